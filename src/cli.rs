@@ -2,28 +2,64 @@ use std::path::PathBuf;
 
 use clap::{ArgAction, Parser};
 
-use crate::model::{Format, Lens};
+use crate::model::{ByteSize, Column, Format, Lens, MdStyle, SortOrder, When};
 
 #[derive(Parser, Debug)]
 #[command(
     name = "smarttree",
     version,
     about = "Project-aware tree output",
-    after_help = "Examples:\n  smarttree\n  smarttree . --lens files --depth 3\n  smarttree --format md --max-children 60\n  smarttree --init\n"
+    after_help = "Examples:\n  smarttree\n  smarttree . --lens files --depth 3\n  smarttree --format md --max-children 60\n  smarttree --format json --unfiltered\n  smarttree --init\n"
 )]
 pub struct Cli {
     /// Path to scan (default: current directory)
     #[arg(value_name = "PATH", default_value = ".")]
     pub path: PathBuf,
 
-    /// Output lens: module or files
+    /// Output lens: which view of the tree to show
     #[arg(long, value_enum)]
     pub lens: Option<Lens>,
 
-    /// Output format: text or md
+    /// Output format: text, md, json, mermaid, dot, html, ndjson, context, csv, tsv or mdtable
     #[arg(long, value_enum)]
     pub format: Option<Format>,
 
+    /// Markdown style: list (nested links) or fence (text tree in a code block)
+    #[arg(long = "md-style", value_enum)]
+    pub md_style: Option<MdStyle>,
+
+    /// Ignore lens filtering in structured output (json) and dump every node
+    #[arg(long, action = ArgAction::SetTrue)]
+    pub unfiltered: bool,
+
+    /// Columns for csv, tsv and mdtable output (comma-separated)
+    #[arg(long, value_enum, value_delimiter = ',', value_name = "COLUMNS")]
+    pub columns: Option<Vec<Column>>,
+
+    /// Approximate token limit for context output
+    #[arg(long = "token-budget", value_name = "N")]
+    pub token_budget: Option<usize>,
+
+    /// Order of children: name, or size (largest first, works with every lens)
+    #[arg(long, value_enum)]
+    pub sort: Option<SortOrder>,
+
+    /// Hide entries smaller than SIZE (e.g. 512, 10K, 1.5M); needs the size lens or --sort size
+    #[arg(long = "min-size", value_name = "SIZE")]
+    pub min_size: Option<ByteSize>,
+
+    /// Git history window for the churn lens, as accepted by `git log --since`
+    #[arg(long, value_name = "WHEN")]
+    pub since: Option<String>,
+
+    /// Show only modules with changes since REF (including uncommitted and untracked files)
+    #[arg(long = "changed-since", value_name = "REF")]
+    pub changed_since: Option<String>,
+
+    /// With --changed-since, also show modules that depend on the changed ones
+    #[arg(long = "include-dependents", action = ArgAction::SetTrue)]
+    pub include_dependents: bool,
+
     /// Max depth to traverse
     #[arg(long)]
     pub depth: Option<usize>,
@@ -75,4 +111,16 @@ pub struct Cli {
     /// Use ASCII tree characters
     #[arg(long, action = ArgAction::SetTrue)]
     pub ascii: bool,
+
+    /// Colorize text output: auto (TTY without NO_COLOR), always or never
+    #[arg(long, value_enum, value_name = "WHEN")]
+    pub color: Option<When>,
+
+    /// Emit OSC 8 file:// hyperlinks in text output: auto (TTY only), always or never
+    #[arg(long, value_enum, value_name = "WHEN")]
+    pub hyperlinks: Option<When>,
+
+    /// Make module hyperlinks point at the manifest file instead of the directory
+    #[arg(long = "link-manifest", action = ArgAction::SetTrue)]
+    pub link_manifest: bool,
 }
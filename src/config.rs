@@ -7,7 +7,7 @@ use serde::Deserialize;
 
 use crate::cli::Cli;
 use crate::error::SmarttreeError;
-use crate::model::{Format, Lens};
+use crate::model::{ByteSize, Column, Format, Lens, MdStyle, SortOrder, When};
 
 const DEFAULT_DEPTH: usize = 4;
 const DEFAULT_MAX_ITEMS: usize = 20_000;
@@ -15,6 +15,10 @@ const DEFAULT_MAX_CHILDREN: usize = 200;
 const DEFAULT_RESPECT_GITIGNORE: bool = true;
 const DEFAULT_HIDDEN: bool = false;
 const DEFAULT_UNICODE: bool = false;
+const DEFAULT_UNFILTERED: bool = false;
+const DEFAULT_LINK_MANIFEST: bool = false;
+const DEFAULT_TOKEN_BUDGET: usize = 8_000;
+const DEFAULT_SINCE: &str = "90.days";
 
 const DEFAULT_KEY_DIRS: &[&str] = &[
     "src", "tests", "test", "docs", "examples", "scripts", "public", "include", "cmd", "bin",
@@ -49,12 +53,24 @@ pub struct Config {
     pub root: PathBuf,
     pub lens: Lens,
     pub format: Format,
+    pub md_style: MdStyle,
+    pub unfiltered: bool,
+    pub columns: Vec<Column>,
+    pub token_budget: usize,
+    pub sort: SortOrder,
+    pub min_size: u64,
+    pub since: String,
+    pub changed_since: Option<String>,
+    pub include_dependents: bool,
     pub depth: usize,
     pub max_items: usize,
     pub max_children: usize,
     pub respect_gitignore: bool,
     pub hidden: bool,
     pub unicode: bool,
+    pub color: When,
+    pub hyperlinks: When,
+    pub link_manifest: bool,
     pub ignore: Vec<String>,
     pub include: Vec<String>,
     pub key_dirs: Vec<String>,
@@ -71,12 +87,22 @@ pub struct InitOutcome {
 pub struct ConfigFile {
     pub lens: Option<Lens>,
     pub format: Option<Format>,
+    pub md_style: Option<MdStyle>,
+    pub unfiltered: Option<bool>,
+    pub columns: Option<Vec<Column>>,
+    pub token_budget: Option<usize>,
+    pub sort: Option<SortOrder>,
+    pub min_size: Option<ByteSize>,
+    pub since: Option<String>,
     pub depth: Option<usize>,
     pub max_items: Option<usize>,
     pub max_children: Option<usize>,
     pub respect_gitignore: Option<bool>,
     pub hidden: Option<bool>,
     pub unicode: Option<bool>,
+    pub color: Option<When>,
+    pub hyperlinks: Option<When>,
+    pub link_manifest: Option<bool>,
     pub ignore: Option<Vec<String>>,
     pub include: Option<Vec<String>>,
     pub key_dirs: Option<Vec<String>>,
@@ -113,6 +139,31 @@ fn load_config_file(path: &Path) -> Result<ConfigFile> {
 fn build_config(root: PathBuf, cli: &Cli, file_config: ConfigFile) -> Config {
     let lens = cli.lens.or(file_config.lens).unwrap_or(Lens::Module);
     let format = cli.format.or(file_config.format).unwrap_or(Format::Text);
+    let md_style = cli
+        .md_style
+        .or(file_config.md_style)
+        .unwrap_or(MdStyle::List);
+    let unfiltered = if cli.unfiltered {
+        true
+    } else {
+        file_config.unfiltered.unwrap_or(DEFAULT_UNFILTERED)
+    };
+    let columns = cli
+        .columns
+        .clone()
+        .or(file_config.columns)
+        .unwrap_or_else(|| Column::ALL.to_vec());
+    let token_budget = cli
+        .token_budget
+        .or(file_config.token_budget)
+        .unwrap_or(DEFAULT_TOKEN_BUDGET);
+    let sort = cli.sort.or(file_config.sort).unwrap_or(SortOrder::Name);
+    let min_size = cli.min_size.or(file_config.min_size).unwrap_or_default().0;
+    let since = cli
+        .since
+        .clone()
+        .or(file_config.since)
+        .unwrap_or_else(|| DEFAULT_SINCE.to_string());
     let depth = cli.depth.or(file_config.depth).unwrap_or(DEFAULT_DEPTH);
     let max_items = cli
         .max_items
@@ -147,6 +198,17 @@ fn build_config(root: PathBuf, cli: &Cli, file_config: ConfigFile) -> Config {
         file_config.unicode.unwrap_or(DEFAULT_UNICODE)
     };
 
+    let color = cli.color.or(file_config.color).unwrap_or(When::Auto);
+    let hyperlinks = cli
+        .hyperlinks
+        .or(file_config.hyperlinks)
+        .unwrap_or(When::Auto);
+    let link_manifest = if cli.link_manifest {
+        true
+    } else {
+        file_config.link_manifest.unwrap_or(DEFAULT_LINK_MANIFEST)
+    };
+
     let mut ignore = Vec::new();
     ignore.extend(DEFAULT_IGNORE.iter().map(|s| s.to_string()));
     if let Some(extra) = file_config.ignore {
@@ -170,12 +232,24 @@ fn build_config(root: PathBuf, cli: &Cli, file_config: ConfigFile) -> Config {
         root,
         lens,
         format,
+        md_style,
+        unfiltered,
+        columns,
+        token_budget,
+        sort,
+        min_size,
+        since,
+        changed_since: cli.changed_since.clone(),
+        include_dependents: cli.include_dependents,
         depth,
         max_items,
         max_children,
         respect_gitignore,
         hidden,
         unicode,
+        color,
+        hyperlinks,
+        link_manifest,
         ignore,
         include,
         key_dirs,
@@ -246,6 +320,12 @@ fn render_config_template(config: &Config) -> String {
     out.push_str("# See README.md for details.\n\n");
     writeln!(out, "lens: {}", lens_label(config.lens)).ok();
     writeln!(out, "format: {}", format_label(config.format)).ok();
+    writeln!(out, "md_style: {}", md_style_label(config.md_style)).ok();
+    writeln!(out, "unfiltered: {}", bool_label(config.unfiltered)).ok();
+    writeln!(out, "token_budget: {}", config.token_budget).ok();
+    writeln!(out, "sort: {}", sort_label(config.sort)).ok();
+    writeln!(out, "min_size: {}", config.min_size).ok();
+    writeln!(out, "since: \"{}\"", escape_yaml(&config.since)).ok();
     writeln!(out, "depth: {}", config.depth).ok();
     writeln!(out, "max_items: {}", config.max_items).ok();
     writeln!(out, "max_children: {}", config.max_children).ok();
@@ -257,6 +337,9 @@ fn render_config_template(config: &Config) -> String {
     .ok();
     writeln!(out, "hidden: {}", bool_label(config.hidden)).ok();
     writeln!(out, "unicode: {}", bool_label(config.unicode)).ok();
+    writeln!(out, "color: {}", when_label(config.color)).ok();
+    writeln!(out, "hyperlinks: {}", when_label(config.hyperlinks)).ok();
+    writeln!(out, "link_manifest: {}", bool_label(config.link_manifest)).ok();
     out.push('\n');
 
     push_list(&mut out, "ignore", &config.ignore);
@@ -268,6 +351,13 @@ fn render_config_template(config: &Config) -> String {
     }
     out.push('\n');
     push_list(&mut out, "key_dirs", &config.key_dirs);
+    out.push('\n');
+    let columns: Vec<String> = config
+        .columns
+        .iter()
+        .map(|c| c.label().to_string())
+        .collect();
+    push_list(&mut out, "columns", &columns);
 
     out
 }
@@ -290,6 +380,15 @@ fn lens_label(lens: Lens) -> &'static str {
     match lens {
         Lens::Module => "module",
         Lens::Files => "files",
+        Lens::Deps => "deps",
+        Lens::Owners => "owners",
+        Lens::Size => "size",
+        Lens::Churn => "churn",
+        Lens::Entrypoints => "entrypoints",
+        Lens::Languages => "languages",
+        Lens::Tests => "tests",
+        Lens::Docs => "docs",
+        Lens::Infra => "infra",
     }
 }
 
@@ -297,6 +396,37 @@ fn format_label(format: Format) -> &'static str {
     match format {
         Format::Text => "text",
         Format::Md => "md",
+        Format::Json => "json",
+        Format::Mermaid => "mermaid",
+        Format::Dot => "dot",
+        Format::Html => "html",
+        Format::Ndjson => "ndjson",
+        Format::Context => "context",
+        Format::Csv => "csv",
+        Format::Tsv => "tsv",
+        Format::Mdtable => "mdtable",
+    }
+}
+
+fn md_style_label(style: MdStyle) -> &'static str {
+    match style {
+        MdStyle::List => "list",
+        MdStyle::Fence => "fence",
+    }
+}
+
+fn sort_label(sort: SortOrder) -> &'static str {
+    match sort {
+        SortOrder::Name => "name",
+        SortOrder::Size => "size",
+    }
+}
+
+fn when_label(when: When) -> &'static str {
+    match when {
+        When::Auto => "auto",
+        When::Always => "always",
+        When::Never => "never",
     }
 }
 
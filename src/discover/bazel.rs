@@ -0,0 +1,161 @@
+use std::fs;
+use std::path::Path;
+
+/// Files that make a directory the root of a Bazel workspace.
+pub const WORKSPACE_FILES: [&str; 3] = ["MODULE.bazel", "WORKSPACE.bazel", "WORKSPACE"];
+
+/// Files that make a directory a Bazel package, preferred name first.
+pub const BUILD_FILES: [&str; 2] = ["BUILD.bazel", "BUILD"];
+
+/// A top-level call in a Starlark file, with its keyword arguments that
+/// hold plain strings.
+struct Call {
+    function: String,
+    strings: Vec<(String, String)>,
+}
+
+/// `(rule kind, target name)` for every rule declared in the package's BUILD
+/// file, in file order. Calls without a `name`, such as `load` or `package`,
+/// are not targets.
+pub fn targets(package: &Path) -> Vec<(String, String)> {
+    let Some(content) = BUILD_FILES
+        .iter()
+        .find_map(|name| fs::read_to_string(package.join(name)).ok())
+    else {
+        return Vec::new();
+    };
+    parse_targets(&content)
+}
+
+fn parse_targets(content: &str) -> Vec<(String, String)> {
+    calls(content)
+        .into_iter()
+        .filter_map(|call| {
+            let name = call
+                .strings
+                .into_iter()
+                .find_map(|(key, value)| (key == "name").then_some(value))?;
+            Some((call.function, name))
+        })
+        .collect()
+}
+
+/// The `name` given to `module(...)` in `MODULE.bazel`.
+pub fn module_name(root: &Path) -> Option<String> {
+    let content = fs::read_to_string(root.join("MODULE.bazel")).ok()?;
+    calls(&content)
+        .into_iter()
+        .find(|call| call.function == "module")?
+        .strings
+        .into_iter()
+        .find_map(|(key, value)| (key == "name").then_some(value))
+}
+
+/// Top-level calls in a Starlark file. Only arguments directly inside the
+/// call are kept; lists, dicts and nested calls are skipped over.
+fn calls(content: &str) -> Vec<Call> {
+    let mut calls = Vec::new();
+    let mut depth = 0usize;
+    let mut identifier = String::new();
+    let mut function: Option<String> = None;
+    let mut arguments = String::new();
+    let mut chars = content.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '#' => {
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        break;
+                    }
+                }
+                identifier.clear();
+            }
+            '"' | '\'' => {
+                let mut value = String::new();
+                for next in chars.by_ref() {
+                    if next == c {
+                        break;
+                    }
+                    value.push(next);
+                }
+                if depth == 1 && function.is_some() {
+                    arguments.push('"');
+                    arguments.push_str(&value);
+                    arguments.push('"');
+                }
+                identifier.clear();
+            }
+            '(' | '[' | '{' => {
+                if depth == 0 && c == '(' && !identifier.is_empty() {
+                    function = Some(std::mem::take(&mut identifier));
+                    arguments.clear();
+                }
+                depth += 1;
+            }
+            ')' | ']' | '}' => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    if let Some(function) = function.take() {
+                        calls.push(Call {
+                            function,
+                            strings: string_arguments(&arguments),
+                        });
+                    }
+                }
+            }
+            _ if depth == 0 => {
+                if c.is_alphanumeric() || c == '_' || c == '.' {
+                    identifier.push(c);
+                } else {
+                    identifier.clear();
+                }
+            }
+            _ if depth == 1 && function.is_some() => arguments.push(c),
+            _ => {}
+        }
+    }
+    calls
+}
+
+/// `key = "value"` pairs from the flattened arguments of a call.
+fn string_arguments(arguments: &str) -> Vec<(String, String)> {
+    arguments
+        .split(',')
+        .filter_map(|argument| {
+            let (key, value) = argument.split_once('=')?;
+            let value = value.trim().strip_prefix('"')?.strip_suffix('"')?;
+            Some((key.trim().to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_rule_kinds_and_target_names() {
+        let build = r#"
+load("@rules_go//go:def.bzl", "go_binary", "go_library")
+
+package(default_visibility = ["//visibility:public"])
+
+# go_test(name = "commented_out")
+go_library(
+    name = "server",
+    srcs = glob(["*.go"], exclude = ["*_test.go"]),
+    deps = ["//lib/auth:go_default_library"],
+)
+
+go_binary(name = 'cli', embed = [":server"])
+"#;
+        assert_eq!(
+            parse_targets(build),
+            vec![
+                ("go_library".to_string(), "server".to_string()),
+                ("go_binary".to_string(), "cli".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::discover::{deps, git};
+use crate::model::{Affected, Tree};
+
+/// Marks the modules owning files that changed since `base` (committed,
+/// staged, unstaged or untracked) as [`Affected::Changed`], and with
+/// `include_dependents` every module depending on them, directly or not, as
+/// [`Affected::Dependent`].
+pub fn mark_affected(tree: &mut Tree, base: &str, include_dependents: bool) -> Result<()> {
+    if !tree.root_path.is_dir() {
+        return Ok(());
+    }
+    let mut changed = changed_paths(&tree.root_path, base)?;
+    changed.sort();
+    changed.dedup();
+
+    let index: HashMap<&Path, usize> = tree
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(id, node)| (node.rel_path.as_path(), id))
+        .collect();
+    let mut parents = vec![None; tree.nodes.len()];
+    for (id, node) in tree.nodes.iter().enumerate() {
+        for child in &node.children {
+            parents[*child] = Some(id);
+        }
+    }
+
+    let mut owners = Vec::new();
+    for path in &changed {
+        // Files may be deleted or sit past the depth limit; start from the
+        // nearest walked ancestor and climb to the owning module.
+        let mut current = path
+            .ancestors()
+            .find_map(|ancestor| index.get(ancestor).copied());
+        while let Some(id) = current {
+            if tree.nodes[id].module.is_some() {
+                owners.push(id);
+                break;
+            }
+            current = parents[id];
+        }
+    }
+    for id in &owners {
+        if let Some(module) = tree.nodes[*id].module.as_mut() {
+            module.affected = Some(Affected::Changed);
+        }
+    }
+
+    if include_dependents {
+        mark_dependents(tree, owners);
+    }
+    Ok(())
+}
+
+/// Paths changed since the merge base with `base`, plus untracked files.
+/// Both commands use `-z` so that paths come back unquoted, and `base` follows
+/// `--end-of-options` so a ref starting with `-` is not taken for an option.
+fn changed_paths(root: &Path, base: &str) -> Result<Vec<PathBuf>> {
+    let diff = git::run(
+        root,
+        &[
+            "diff",
+            "--name-only",
+            "-z",
+            "--no-renames",
+            "--relative",
+            "--merge-base",
+            "--end-of-options",
+            base,
+        ],
+    )?;
+    let untracked = git::run(root, &["ls-files", "-z", "--others", "--exclude-standard"])?;
+    Ok(diff
+        .split('\0')
+        .chain(untracked.split('\0'))
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+        .collect())
+}
+
+fn mark_dependents(tree: &mut Tree, changed: Vec<usize>) {
+    deps::resolve_dependencies(tree);
+    let by_path: HashMap<PathBuf, usize> = tree
+        .nodes
+        .iter()
+        .enumerate()
+        .filter(|(_, node)| node.module.is_some())
+        .map(|(id, node)| (node.rel_path.clone(), id))
+        .collect();
+
+    let mut queue: VecDeque<usize> = changed.into();
+    while let Some(id) = queue.pop_front() {
+        let dependents = tree.nodes[id]
+            .module
+            .as_ref()
+            .map(|module| module.dependents.clone())
+            .unwrap_or_default();
+        for path in dependents {
+            let Some(&dependent) = by_path.get(&path) else {
+                continue;
+            };
+            if let Some(module) = tree.nodes[dependent].module.as_mut() {
+                if module.affected.is_none() {
+                    module.affected = Some(Affected::Dependent);
+                    queue.push_back(dependent);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::discover::git;
+use crate::model::{ChurnInfo, Tree};
+
+/// A module is hot when it saw at least this share of the commits of the
+/// busiest module.
+const HOT_SHARE: f64 = 0.5;
+
+/// One commit's `--numstat` lines: `(path, added, deleted)`.
+type CommitStats = Vec<(PathBuf, u64, u64)>;
+
+/// Reads `git log --numstat` for the `since` window and attaches commit and
+/// line counts to every node. A directory counts each commit touching
+/// anything below it once. Paths missing from the tree (deleted files, or
+/// entries past the depth limit) count towards their nearest walked parent.
+pub fn assign_churn(tree: &mut Tree, since: &str) -> Result<()> {
+    if !tree.root_path.is_dir() {
+        return Ok(());
+    }
+    let since = format!("--since={since}");
+    let log = git::run(
+        &tree.root_path,
+        &[
+            "log",
+            "--numstat",
+            "-z",
+            "--no-renames",
+            "--relative",
+            "--format=@%H",
+            &since,
+        ],
+    )?;
+
+    let index: HashMap<&Path, usize> = tree
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(id, node)| (node.rel_path.as_path(), id))
+        .collect();
+    let mut parents = vec![None; tree.nodes.len()];
+    for (id, node) in tree.nodes.iter().enumerate() {
+        for child in &node.children {
+            parents[*child] = Some(id);
+        }
+    }
+
+    let mut churn = vec![ChurnInfo::default(); tree.nodes.len()];
+    for commit in parse_numstat(&log) {
+        let mut touched = HashSet::new();
+        for (path, added, deleted) in commit {
+            let mut current = Some(nearest_node(&index, &path));
+            while let Some(id) = current {
+                churn[id].added += added;
+                churn[id].deleted += deleted;
+                touched.insert(id);
+                current = parents[id];
+            }
+        }
+        for id in touched {
+            churn[id].commits += 1;
+        }
+    }
+
+    for (node, info) in tree.nodes.iter_mut().zip(churn) {
+        node.churn = Some(info);
+    }
+    mark_hot_modules(tree);
+    Ok(())
+}
+
+fn nearest_node(index: &HashMap<&Path, usize>, path: &Path) -> usize {
+    path.ancestors()
+        .find_map(|ancestor| index.get(ancestor).copied())
+        .unwrap_or(0)
+}
+
+fn mark_hot_modules(tree: &mut Tree) {
+    let busiest = tree
+        .nodes
+        .iter()
+        .filter(|node| node.module.is_some())
+        .filter_map(|node| node.churn.map(|c| c.commits))
+        .max()
+        .unwrap_or(0);
+    if busiest == 0 {
+        return;
+    }
+    for node in tree.nodes.iter_mut().filter(|node| node.module.is_some()) {
+        if let Some(churn) = node.churn.as_mut() {
+            churn.hot = churn.commits as f64 >= busiest as f64 * HOT_SHARE;
+        }
+    }
+}
+
+/// Splits `git log --numstat -z --format=@%H` output into commits. With
+/// `-z` every record ends in a NUL and paths are not quoted, so names with
+/// spaces or non-ASCII characters come through as they are. Binary files
+/// report `-` for their line counts and count as zero lines.
+fn parse_numstat(log: &str) -> Vec<CommitStats> {
+    let mut commits: Vec<CommitStats> = Vec::new();
+    for record in log.split('\0') {
+        let line = record.trim_start_matches('\n');
+        if line.starts_with('@') {
+            commits.push(Vec::new());
+            continue;
+        }
+        let mut fields = line.splitn(3, '\t');
+        let (Some(added), Some(deleted), Some(path)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        if let Some(commit) = commits.last_mut() {
+            commit.push((
+                PathBuf::from(path),
+                added.parse().unwrap_or(0),
+                deleted.parse().unwrap_or(0),
+            ));
+        }
+    }
+    commits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_numstat_commits() {
+        let commits = parse_numstat(
+            "@aaa\0\n3\t1\tsrc/lib.rs\0-\t-\tlogo.png\0\
+             3\t0\tpkgs/café/a b.js\0@bbb\0\n10\t0\tREADME.md\0",
+        );
+        assert_eq!(commits.len(), 2);
+        assert_eq!(
+            commits[0],
+            vec![
+                (PathBuf::from("src/lib.rs"), 3, 1),
+                (PathBuf::from("logo.png"), 0, 0),
+                (PathBuf::from("pkgs/café/a b.js"), 3, 0),
+            ]
+        );
+        assert_eq!(commits[1], vec![(PathBuf::from("README.md"), 10, 0)]);
+    }
+}
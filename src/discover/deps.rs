@@ -0,0 +1,464 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::model::Tree;
+
+/// Package ecosystems whose manifests declare dependencies. A module may take
+/// part in several (for example a napi crate with both `package.json` and
+/// `Cargo.toml`), so names are indexed per ecosystem rather than per kind.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+enum Ecosystem {
+    Node,
+    Rust,
+    Python,
+    Go,
+}
+
+fn ecosystem_for_marker(marker: &str) -> Option<Ecosystem> {
+    match marker {
+        "package.json" => Some(Ecosystem::Node),
+        "Cargo.toml" => Some(Ecosystem::Rust),
+        "pyproject.toml" => Some(Ecosystem::Python),
+        "go.mod" => Some(Ecosystem::Go),
+        _ => None,
+    }
+}
+
+/// A dependency as declared in a manifest: either a package name to look up
+/// among the modules, or a path relative to the tree root.
+#[derive(Debug, Eq, PartialEq)]
+enum DepRef {
+    Name(String),
+    Path(PathBuf),
+}
+
+/// Fills in `dependencies` and `dependents` for every annotated module by
+/// reading its manifests and resolving each declared dependency to another
+/// module in the tree. External packages are ignored.
+pub fn resolve_dependencies(tree: &mut Tree) {
+    let modules: Vec<(usize, PathBuf, Vec<String>)> = tree
+        .nodes
+        .iter()
+        .enumerate()
+        .filter_map(|(id, node)| {
+            let module = node.module.as_ref()?;
+            Some((id, node.rel_path.clone(), module.markers.clone()))
+        })
+        .collect();
+
+    let workspace_deps = cargo_workspace_dependencies(&tree.root_path);
+
+    let mut by_name: HashMap<(Ecosystem, String), PathBuf> = HashMap::new();
+    let mut declared: Vec<Vec<(Ecosystem, DepRef)>> = Vec::with_capacity(modules.len());
+    for (_, rel, markers) in &modules {
+        let module_path = tree.root_path.join(rel);
+        let mut refs = Vec::new();
+        for ecosystem in markers.iter().filter_map(|m| ecosystem_for_marker(m)) {
+            let manifest = match ecosystem {
+                Ecosystem::Node => read_package_json(&module_path),
+                Ecosystem::Rust => read_cargo_toml(&module_path, rel, &workspace_deps),
+                Ecosystem::Python => read_pyproject(&module_path),
+                Ecosystem::Go => read_go_mod(&module_path),
+            };
+            let Some(manifest) = manifest else {
+                continue;
+            };
+            if let Some(name) = manifest.name {
+                by_name.entry((ecosystem, name)).or_insert(rel.clone());
+            }
+            for dep in manifest.deps {
+                let dep = match dep {
+                    DepRef::Path(path) => match normalize(&module_path, &path, &tree.root_path) {
+                        Some(path) => DepRef::Path(path),
+                        None => continue,
+                    },
+                    other => other,
+                };
+                refs.push((ecosystem, dep));
+            }
+        }
+        declared.push(refs);
+    }
+
+    let module_paths: BTreeSet<&PathBuf> = modules.iter().map(|(_, rel, _)| rel).collect();
+    let mut dependencies: HashMap<usize, BTreeSet<PathBuf>> = HashMap::new();
+    let mut dependents: HashMap<PathBuf, BTreeSet<PathBuf>> = HashMap::new();
+    for ((id, rel, _), refs) in modules.iter().zip(&declared) {
+        for (ecosystem, dep) in refs {
+            let target = match dep {
+                DepRef::Name(name) => by_name.get(&(*ecosystem, name.clone())),
+                DepRef::Path(path) => module_paths.get(path).copied(),
+            };
+            let Some(target) = target else {
+                continue;
+            };
+            if target == rel {
+                continue;
+            }
+            dependencies.entry(*id).or_default().insert(target.clone());
+            dependents
+                .entry(target.clone())
+                .or_default()
+                .insert(rel.clone());
+        }
+    }
+
+    for (id, rel, _) in &modules {
+        let Some(module) = tree.nodes[*id].module.as_mut() else {
+            continue;
+        };
+        module.dependencies = dependencies
+            .remove(id)
+            .map(|set| set.into_iter().collect())
+            .unwrap_or_default();
+        module.dependents = dependents
+            .remove(rel)
+            .map(|set| set.into_iter().collect())
+            .unwrap_or_default();
+    }
+}
+
+/// Joins a manifest-relative path onto the module directory and resolves it
+/// lexically to a path relative to the tree root. Paths that leave the tree
+/// are dropped.
+fn normalize(module_path: &Path, dep_path: &Path, root: &Path) -> Option<PathBuf> {
+    let rel_module = module_path.strip_prefix(root).ok()?;
+    let mut parts: Vec<Component> = rel_module.components().collect();
+    for component in dep_path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                parts.pop()?;
+            }
+            Component::Normal(_) => parts.push(component),
+            Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    Some(parts.iter().collect())
+}
+
+struct Manifest {
+    name: Option<String>,
+    deps: Vec<DepRef>,
+}
+
+const NODE_DEP_SECTIONS: [&str; 4] = [
+    "dependencies",
+    "devDependencies",
+    "peerDependencies",
+    "optionalDependencies",
+];
+
+fn read_package_json(module_path: &Path) -> Option<Manifest> {
+    let content = fs::read_to_string(module_path.join("package.json")).ok()?;
+    let value: serde_json::Value = serde_json::from_str(&content).ok()?;
+    let name = value
+        .get("name")
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+
+    let mut deps = Vec::new();
+    for section in NODE_DEP_SECTIONS {
+        let Some(entries) = value.get(section).and_then(|v| v.as_object()) else {
+            continue;
+        };
+        for (dep_name, spec) in entries {
+            let spec = spec.as_str().unwrap_or_default();
+            let local = spec
+                .strip_prefix("file:")
+                .or_else(|| spec.strip_prefix("link:"));
+            match local {
+                Some(path) => deps.push(DepRef::Path(PathBuf::from(path))),
+                // `workspace:` specs and plain version ranges both name a
+                // package; only those published by a module in the tree
+                // resolve.
+                None => deps.push(DepRef::Name(dep_name.clone())),
+            }
+        }
+    }
+    Some(Manifest { name, deps })
+}
+
+const CARGO_DEP_SECTIONS: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+
+/// `[workspace.dependencies]` entries with a `path`, keyed by name. Paths are
+/// relative to the root manifest.
+fn cargo_workspace_dependencies(root: &Path) -> HashMap<String, PathBuf> {
+    let mut paths = HashMap::new();
+    let Ok(content) = fs::read_to_string(root.join("Cargo.toml")) else {
+        return paths;
+    };
+    let Ok(value) = toml::from_str::<toml::Value>(&content) else {
+        return paths;
+    };
+    let Some(entries) = value
+        .get("workspace")
+        .and_then(|w| w.get("dependencies"))
+        .and_then(|d| d.as_table())
+    else {
+        return paths;
+    };
+    for (name, spec) in entries {
+        if let Some(path) = spec.get("path").and_then(|p| p.as_str()) {
+            paths.insert(name.clone(), PathBuf::from(path));
+        }
+    }
+    paths
+}
+
+fn read_cargo_toml(
+    module_path: &Path,
+    rel: &Path,
+    workspace_deps: &HashMap<String, PathBuf>,
+) -> Option<Manifest> {
+    let content = fs::read_to_string(module_path.join("Cargo.toml")).ok()?;
+    let value: toml::Value = toml::from_str(&content).ok()?;
+    let name = value
+        .get("package")
+        .and_then(|p| p.get("name"))
+        .and_then(|n| n.as_str())
+        .map(str::to_string);
+
+    let mut tables = Vec::new();
+    for section in CARGO_DEP_SECTIONS {
+        tables.extend(value.get(section).and_then(|v| v.as_table()));
+    }
+    if let Some(targets) = value.get("target").and_then(|v| v.as_table()) {
+        for target in targets.values() {
+            for section in CARGO_DEP_SECTIONS {
+                tables.extend(target.get(section).and_then(|v| v.as_table()));
+            }
+        }
+    }
+
+    let to_root = path_to_root(rel);
+    let mut deps = Vec::new();
+    for table in tables {
+        for (key, spec) in table {
+            let package = spec.get("package").and_then(|p| p.as_str()).unwrap_or(key);
+            if let Some(path) = spec.get("path").and_then(|p| p.as_str()) {
+                deps.push(DepRef::Path(PathBuf::from(path)));
+            } else if spec.get("workspace").and_then(|w| w.as_bool()) == Some(true) {
+                match workspace_deps.get(key) {
+                    Some(path) => deps.push(DepRef::Path(to_root.join(path))),
+                    None => deps.push(DepRef::Name(package.to_string())),
+                }
+            } else {
+                deps.push(DepRef::Name(package.to_string()));
+            }
+        }
+    }
+    Some(Manifest { name, deps })
+}
+
+/// `../` once per component of `rel`, leading from a module back to the
+/// tree root.
+fn path_to_root(rel: &Path) -> PathBuf {
+    rel.components().map(|_| Component::ParentDir).collect()
+}
+
+fn read_pyproject(module_path: &Path) -> Option<Manifest> {
+    let content = fs::read_to_string(module_path.join("pyproject.toml")).ok()?;
+    let value: toml::Value = toml::from_str(&content).ok()?;
+    let project = value.get("project");
+    let poetry = value.get("tool").and_then(|t| t.get("poetry"));
+
+    let name = project
+        .or(poetry)
+        .and_then(|t| t.get("name"))
+        .and_then(|n| n.as_str())
+        .map(normalize_python_name);
+
+    let mut deps = Vec::new();
+    if let Some(project) = project {
+        let mut requirements: Vec<&toml::Value> = Vec::new();
+        if let Some(list) = project.get("dependencies").and_then(|d| d.as_array()) {
+            requirements.extend(list);
+        }
+        if let Some(groups) = project
+            .get("optional-dependencies")
+            .and_then(|d| d.as_table())
+        {
+            for list in groups.values().filter_map(|g| g.as_array()) {
+                requirements.extend(list);
+            }
+        }
+        for requirement in requirements.into_iter().filter_map(|r| r.as_str()) {
+            if let Some(name) = pep508_name(requirement) {
+                deps.push(DepRef::Name(name));
+            }
+        }
+    }
+
+    if let Some(poetry) = poetry {
+        let mut tables = Vec::new();
+        tables.extend(poetry.get("dependencies").and_then(|d| d.as_table()));
+        tables.extend(poetry.get("dev-dependencies").and_then(|d| d.as_table()));
+        if let Some(groups) = poetry.get("group").and_then(|g| g.as_table()) {
+            for group in groups.values() {
+                tables.extend(group.get("dependencies").and_then(|d| d.as_table()));
+            }
+        }
+        for table in tables {
+            for (key, spec) in table {
+                if key == "python" {
+                    continue;
+                }
+                match spec.get("path").and_then(|p| p.as_str()) {
+                    Some(path) => deps.push(DepRef::Path(PathBuf::from(path))),
+                    None => deps.push(DepRef::Name(normalize_python_name(key))),
+                }
+            }
+        }
+    }
+
+    Some(Manifest { name, deps })
+}
+
+/// The distribution name at the start of a PEP 508 requirement, normalized.
+fn pep508_name(requirement: &str) -> Option<String> {
+    let end = requirement
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.'))
+        .unwrap_or(requirement.len());
+    let name = requirement[..end].trim();
+    if name.is_empty() {
+        None
+    } else {
+        Some(normalize_python_name(name))
+    }
+}
+
+/// PEP 503 normalization: case-insensitive, with runs of `-`, `_` and `.`
+/// treated as a single `-`.
+fn normalize_python_name(name: &str) -> String {
+    let mut normalized = String::with_capacity(name.len());
+    let mut separator = false;
+    for c in name.trim().chars() {
+        if matches!(c, '-' | '_' | '.') {
+            separator = true;
+            continue;
+        }
+        if separator && !normalized.is_empty() {
+            normalized.push('-');
+        }
+        separator = false;
+        normalized.push(c.to_ascii_lowercase());
+    }
+    normalized
+}
+
+fn read_go_mod(module_path: &Path) -> Option<Manifest> {
+    let content = fs::read_to_string(module_path.join("go.mod")).ok()?;
+    Some(parse_go_mod(&content))
+}
+
+fn parse_go_mod(content: &str) -> Manifest {
+    let mut name = None;
+    let mut requires = Vec::new();
+    let mut replaces: HashMap<String, PathBuf> = HashMap::new();
+    let mut block: Option<&str> = None;
+
+    for line in content.lines() {
+        let line = line.split("//").next().unwrap_or_default().trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(directive) = block {
+            if line == ")" {
+                block = None;
+            } else {
+                go_directive(directive, line, &mut requires, &mut replaces);
+            }
+            continue;
+        }
+        let (directive, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let rest = rest.trim();
+        match directive {
+            "module" => name = Some(rest.trim_matches('"').to_string()),
+            "require" | "replace" if rest == "(" => block = Some(directive),
+            "require" | "replace" => go_directive(directive, rest, &mut requires, &mut replaces),
+            _ => {}
+        }
+    }
+
+    let deps = requires
+        .into_iter()
+        .map(|module| match replaces.remove(&module) {
+            Some(path) => DepRef::Path(path),
+            None => DepRef::Name(module),
+        })
+        .collect();
+    Manifest { name, deps }
+}
+
+fn go_directive(
+    directive: &str,
+    spec: &str,
+    requires: &mut Vec<String>,
+    replaces: &mut HashMap<String, PathBuf>,
+) {
+    match directive {
+        "require" => {
+            if let Some(module) = spec.split_whitespace().next() {
+                requires.push(module.to_string());
+            }
+        }
+        "replace" => {
+            let Some((from, to)) = spec.split_once("=>") else {
+                return;
+            };
+            let Some(module) = from.split_whitespace().next() else {
+                return;
+            };
+            let target = to.split_whitespace().next().unwrap_or_default();
+            if target.starts_with("./") || target.starts_with("../") {
+                replaces.insert(module.to_string(), PathBuf::from(target));
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_go_mod_requires_and_local_replaces() {
+        let manifest = parse_go_mod(
+            r#"module example.com/svc
+
+go 1.22
+
+require (
+    example.com/lib v0.0.0 // indirect
+    github.com/pkg/errors v0.9.1
+)
+require example.com/other v1.0.0
+
+replace example.com/lib => ../lib
+replace github.com/pkg/errors => github.com/fork/errors v0.9.2
+"#,
+        );
+        assert_eq!(manifest.name.as_deref(), Some("example.com/svc"));
+        assert_eq!(
+            manifest.deps,
+            vec![
+                DepRef::Path(PathBuf::from("../lib")),
+                DepRef::Name("github.com/pkg/errors".to_string()),
+                DepRef::Name("example.com/other".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn normalizes_pep508_names() {
+        assert_eq!(pep508_name("Py_Lib>=1.0").as_deref(), Some("py-lib"));
+        assert_eq!(
+            pep508_name("requests[socks] ; python_version > '3.8'").as_deref(),
+            Some("requests")
+        );
+        assert_eq!(normalize_python_name("zope.interface"), "zope-interface");
+    }
+}
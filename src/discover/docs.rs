@@ -0,0 +1,68 @@
+use std::fs;
+use std::path::Path;
+
+use crate::discover::summary::{readme_candidates, readme_title};
+use crate::model::{DocsInfo, Tree};
+
+/// Name prefixes (lowercased) of changelog files.
+const CHANGELOG_PREFIXES: [&str; 3] = ["changelog", "changes", "history"];
+
+/// Name prefixes (lowercased) of license files.
+const LICENSE_PREFIXES: [&str; 3] = ["license", "licence", "copying"];
+
+/// Fills in `docs` for every annotated module from the files directly inside
+/// it, read straight from disk so that ignored or depth-limited entries do not
+/// hide them.
+pub fn find_docs(tree: &mut Tree) {
+    let root_path = tree.root_path.clone();
+    for node in &mut tree.nodes {
+        let Some(module) = node.module.as_mut() else {
+            continue;
+        };
+        let module_path = root_path.join(&node.rel_path);
+        let readme = readme_candidates(&module_path).into_iter().next();
+        let title = readme
+            .as_ref()
+            .and_then(|name| readme_title(&module_path.join(name)));
+        let (files, dirs) = list_entries(&module_path);
+        module.docs = Some(DocsInfo {
+            readme,
+            title,
+            changelog: find_prefixed(&files, &CHANGELOG_PREFIXES),
+            docs_dir: dirs
+                .into_iter()
+                .find(|name| matches!(name.to_lowercase().as_str(), "docs" | "doc")),
+            license: find_prefixed(&files, &LICENSE_PREFIXES),
+        });
+    }
+}
+
+/// File and directory names directly inside `dir`, each sorted.
+fn list_entries(dir: &Path) -> (Vec<String>, Vec<String>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return (Vec::new(), Vec::new());
+    };
+    let mut files = Vec::new();
+    let mut dirs = Vec::new();
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if entry.path().is_dir() {
+            dirs.push(name);
+        } else {
+            files.push(name);
+        }
+    }
+    files.sort();
+    dirs.sort();
+    (files, dirs)
+}
+
+fn find_prefixed(files: &[String], prefixes: &[&str]) -> Option<String> {
+    files
+        .iter()
+        .find(|name| {
+            let lower = name.to_lowercase();
+            prefixes.iter().any(|prefix| lower.starts_with(prefix))
+        })
+        .cloned()
+}
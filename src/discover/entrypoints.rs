@@ -0,0 +1,283 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use crate::model::{Entrypoint, EntrypointKind, Tree};
+
+/// Fills in `entrypoints` for every annotated module from its manifests and
+/// conventional file layout, read straight from disk so that files past the
+/// depth limit are still found.
+pub fn find_entrypoints(tree: &mut Tree) {
+    let root_path = tree.root_path.clone();
+    for node in &mut tree.nodes {
+        let Some(module) = node.module.as_mut() else {
+            continue;
+        };
+        let module_path = root_path.join(&node.rel_path);
+        let mut entrypoints = Vec::new();
+        for marker in &module.markers {
+            match marker.as_str() {
+                "package.json" => entrypoints.extend(node_entrypoints(&module_path)),
+                "Cargo.toml" => entrypoints.extend(rust_entrypoints(&module_path)),
+                "pyproject.toml" => entrypoints.extend(python_entrypoints(&module_path)),
+                "go.mod" => entrypoints.extend(go_entrypoints(&module_path)),
+                _ => {}
+            }
+        }
+        group_by_kind(&mut entrypoints);
+        module.entrypoints = entrypoints;
+    }
+}
+
+/// Orders entrypoints by kind, keeping manifest order within each kind, and
+/// drops repeats wherever they appear.
+fn group_by_kind(entrypoints: &mut Vec<Entrypoint>) {
+    entrypoints.sort_by_key(|entry| entry.kind);
+    let mut seen = BTreeSet::new();
+    entrypoints.retain(|entry| seen.insert(entry.clone()));
+}
+
+fn entrypoint(kind: EntrypointKind, name: &str, target: Option<&str>) -> Entrypoint {
+    Entrypoint {
+        kind,
+        name: name.to_string(),
+        target: target.map(|t| t.trim_start_matches("./").to_string()),
+    }
+}
+
+fn node_entrypoints(module_path: &Path) -> Vec<Entrypoint> {
+    let Ok(content) = fs::read_to_string(module_path.join("package.json")) else {
+        return Vec::new();
+    };
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(&content) else {
+        return Vec::new();
+    };
+    let mut found = Vec::new();
+
+    match value.get("bin") {
+        Some(serde_json::Value::String(path)) => {
+            // A bare `bin` path installs a command named after the package,
+            // without its scope.
+            let name = value
+                .get("name")
+                .and_then(|n| n.as_str())
+                .map(|n| n.rsplit('/').next().unwrap_or(n))
+                .unwrap_or("bin");
+            found.push(entrypoint(EntrypointKind::Bin, name, Some(path)));
+        }
+        Some(serde_json::Value::Object(bins)) => {
+            for (name, path) in bins {
+                found.push(entrypoint(EntrypointKind::Bin, name, path.as_str()));
+            }
+        }
+        _ => {}
+    }
+
+    if let Some(main) = value.get("main").and_then(|m| m.as_str()) {
+        let main = main.trim_start_matches("./");
+        found.push(entrypoint(EntrypointKind::Main, main, None));
+    }
+
+    match value.get("exports") {
+        Some(serde_json::Value::String(path)) => {
+            found.push(entrypoint(EntrypointKind::Export, ".", Some(path)));
+        }
+        Some(serde_json::Value::Object(map)) if map.keys().all(|k| k.starts_with('.')) => {
+            for (subpath, target) in map {
+                let target = export_target(target);
+                found.push(entrypoint(EntrypointKind::Export, subpath, target));
+            }
+        }
+        Some(conditions @ serde_json::Value::Object(_)) => {
+            let target = export_target(conditions);
+            found.push(entrypoint(EntrypointKind::Export, ".", target));
+        }
+        _ => {}
+    }
+
+    if let Some(scripts) = value.get("scripts").and_then(|s| s.as_object()) {
+        for name in scripts.keys() {
+            found.push(entrypoint(EntrypointKind::Script, name, None));
+        }
+    }
+    found
+}
+
+/// The file behind an `exports` entry, preferring the usual conditions.
+fn export_target(value: &serde_json::Value) -> Option<&str> {
+    match value {
+        serde_json::Value::String(path) => Some(path),
+        serde_json::Value::Object(conditions) => ["import", "require", "default", "node"]
+            .iter()
+            .find_map(|key| conditions.get(*key))
+            .and_then(export_target),
+        _ => None,
+    }
+}
+
+fn rust_entrypoints(module_path: &Path) -> Vec<Entrypoint> {
+    let Ok(content) = fs::read_to_string(module_path.join("Cargo.toml")) else {
+        return Vec::new();
+    };
+    let Ok(value) = toml::from_str::<toml::Value>(&content) else {
+        return Vec::new();
+    };
+    let package = value
+        .get("package")
+        .and_then(|p| p.get("name"))
+        .and_then(|n| n.as_str());
+    let mut found = Vec::new();
+    let mut declared_paths = Vec::new();
+
+    for bin in value
+        .get("bin")
+        .and_then(|b| b.as_array())
+        .into_iter()
+        .flatten()
+    {
+        let Some(name) = bin.get("name").and_then(|n| n.as_str()) else {
+            continue;
+        };
+        let path = bin
+            .get("path")
+            .and_then(|p| p.as_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("src/bin/{name}.rs"));
+        found.push(entrypoint(EntrypointKind::Bin, name, Some(&path)));
+        declared_paths.push(path);
+    }
+
+    // Cargo's automatic targets, unless a `[[bin]]` already points there.
+    let mut auto = Vec::new();
+    if module_path.join("src/main.rs").is_file() {
+        if let Some(package) = package {
+            auto.push((package.to_string(), "src/main.rs".to_string()));
+        }
+    }
+    if let Ok(entries) = fs::read_dir(module_path.join("src/bin")) {
+        let mut names: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .filter_map(|name| name.strip_suffix(".rs").map(str::to_string))
+            .collect();
+        names.sort();
+        for name in names {
+            auto.push((name.clone(), format!("src/bin/{name}.rs")));
+        }
+    }
+    for (name, path) in auto {
+        if !declared_paths.contains(&path) && !found.iter().any(|e| e.name == name) {
+            found.push(entrypoint(EntrypointKind::Bin, &name, Some(&path)));
+        }
+    }
+    found
+}
+
+fn python_entrypoints(module_path: &Path) -> Vec<Entrypoint> {
+    let mut found = Vec::new();
+    let value = fs::read_to_string(module_path.join("pyproject.toml"))
+        .ok()
+        .and_then(|content| toml::from_str::<toml::Value>(&content).ok());
+    if let Some(value) = value {
+        let project = value.get("project").and_then(|p| p.get("scripts"));
+        let poetry = value
+            .get("tool")
+            .and_then(|t| t.get("poetry"))
+            .and_then(|p| p.get("scripts"));
+        for scripts in [project, poetry].into_iter().flatten() {
+            for (name, target) in scripts.as_table().into_iter().flatten() {
+                found.push(entrypoint(EntrypointKind::Bin, name, target.as_str()));
+            }
+        }
+    }
+
+    // `python -m <package>` entry points, in flat and src layouts.
+    let mut mains = Vec::new();
+    for base in ["", "src"] {
+        let Ok(entries) = fs::read_dir(module_path.join(base)) else {
+            continue;
+        };
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let package = entry.file_name().to_string_lossy().into_owned();
+            if entry.path().join("__main__.py").is_file() {
+                let target = Path::new(base).join(&package).join("__main__.py");
+                mains.push((package, target.to_string_lossy().replace('\\', "/")));
+            }
+        }
+    }
+    if module_path.join("__main__.py").is_file() {
+        mains.push((".".to_string(), "__main__.py".to_string()));
+    }
+    mains.sort();
+    for (package, target) in mains {
+        found.push(entrypoint(EntrypointKind::Main, &package, Some(&target)));
+    }
+    found
+}
+
+fn go_entrypoints(module_path: &Path) -> Vec<Entrypoint> {
+    let mut found = Vec::new();
+    if is_go_main_package(module_path) {
+        let name = module_path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        found.push(entrypoint(EntrypointKind::Bin, &name, Some(".")));
+    }
+    if let Ok(entries) = fs::read_dir(module_path.join("cmd")) {
+        let mut commands: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| is_go_main_package(&entry.path()))
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .collect();
+        commands.sort();
+        for name in commands {
+            let target = format!("cmd/{name}");
+            found.push(entrypoint(EntrypointKind::Bin, &name, Some(&target)));
+        }
+    }
+    found
+}
+
+/// Whether `dir` holds a non-test `.go` file declaring `package main`.
+fn is_go_main_package(dir: &Path) -> bool {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return false;
+    };
+    entries.filter_map(|entry| entry.ok()).any(|entry| {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        name.ends_with(".go")
+            && !name.ends_with("_test.go")
+            && fs::read_to_string(entry.path()).is_ok_and(|content| {
+                content
+                    .lines()
+                    .map(str::trim)
+                    .find(|line| line.starts_with("package "))
+                    == Some("package main")
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_repeats_that_are_not_adjacent() {
+        let mut entrypoints = vec![
+            entrypoint(EntrypointKind::Bin, "cli", Some("src/main.rs")),
+            entrypoint(EntrypointKind::Script, "build", None),
+            entrypoint(EntrypointKind::Bin, "serve", Some("bin/serve.js")),
+            entrypoint(EntrypointKind::Bin, "cli", Some("src/main.rs")),
+        ];
+        group_by_kind(&mut entrypoints);
+        assert_eq!(
+            entrypoints,
+            vec![
+                entrypoint(EntrypointKind::Bin, "cli", Some("src/main.rs")),
+                entrypoint(EntrypointKind::Bin, "serve", Some("bin/serve.js")),
+                entrypoint(EntrypointKind::Script, "build", None),
+            ]
+        );
+    }
+}
@@ -0,0 +1,24 @@
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::Result;
+
+use crate::error::SmarttreeError;
+
+/// Runs `git` in `dir` and returns its stdout.
+pub fn run(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|source| SmarttreeError::GitSpawn { source })?;
+    if !output.status.success() {
+        return Err(SmarttreeError::GitFailed {
+            command: args.first().copied().unwrap_or_default().to_string(),
+            message: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        }
+        .into());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
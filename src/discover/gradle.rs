@@ -0,0 +1,196 @@
+/// Settings files, Kotlin DSL first as Gradle prefers it.
+pub const SETTINGS_FILES: [&str; 2] = ["settings.gradle.kts", "settings.gradle"];
+
+/// Build files, Kotlin DSL first as Gradle prefers it.
+pub const BUILD_FILES: [&str; 2] = ["build.gradle.kts", "build.gradle"];
+
+/// Splits a script into statements with `//` and `/* */` comments removed. A
+/// statement runs across lines while parentheses are open or a line ends with
+/// a comma, so that `include(\n  ":a",\n  ":b"\n)` comes back as one
+/// statement.
+pub fn statements(content: &str) -> Vec<String> {
+    let content = strip_block_comments(content);
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut depth: i32 = 0;
+    for line in content.lines() {
+        let line = strip_comment(line);
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(line);
+        depth += paren_balance(line);
+        if depth <= 0 && !line.ends_with(',') {
+            statements.push(std::mem::take(&mut current));
+            depth = 0;
+        }
+    }
+    if !current.is_empty() {
+        statements.push(current);
+    }
+    statements
+}
+
+/// The contents of every single- or double-quoted string in `text`.
+pub fn quoted_strings(text: &str) -> Vec<String> {
+    let mut strings = Vec::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '"' && c != '\'' {
+            continue;
+        }
+        let mut value = String::new();
+        for next in chars.by_ref() {
+            if next == c {
+                break;
+            }
+            value.push(next);
+        }
+        strings.push(value);
+    }
+    strings
+}
+
+/// The string assigned to `key` in a statement such as `key = "value"`,
+/// `key "value"` or `key.set("value")`.
+pub fn assigned_string(statement: &str, key: &str) -> Option<String> {
+    let rest = statement.strip_prefix(key)?;
+    let rest = rest.trim_start();
+    let value = rest
+        .strip_prefix('=')
+        .or_else(|| rest.strip_prefix(".set("))
+        .unwrap_or(rest)
+        .trim_start();
+    if !value.starts_with(['"', '\'']) {
+        return None;
+    }
+    quoted_strings(value).into_iter().next()
+}
+
+/// `content` with every `/* */` comment outside of a string or `//` comment
+/// removed. Line breaks inside a comment are kept so that line structure is
+/// unchanged.
+fn strip_block_comments(content: &str) -> String {
+    let mut stripped = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let mut quote = None;
+    while let Some(c) = chars.next() {
+        match quote {
+            Some(open) => {
+                if c == open {
+                    quote = None;
+                }
+                stripped.push(c);
+            }
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                stripped.push(c);
+            }
+            None if c == '/' && chars.peek() == Some(&'/') => {
+                // Keep line comments for `strip_comment`, but do not look
+                // for block comments inside them.
+                stripped.push(c);
+                while let Some(&next) = chars.peek() {
+                    if next == '\n' {
+                        break;
+                    }
+                    stripped.push(next);
+                    chars.next();
+                }
+            }
+            None if c == '/' && chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = ' ';
+                for next in chars.by_ref() {
+                    if previous == '*' && next == '/' {
+                        break;
+                    }
+                    if next == '\n' {
+                        stripped.push('\n');
+                    }
+                    previous = next;
+                }
+                stripped.push(' ');
+            }
+            None => stripped.push(c),
+        }
+    }
+    stripped
+}
+
+/// `line` up to any `//` comment outside of a string.
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut previous = ' ';
+    for (index, c) in line.char_indices() {
+        match quote {
+            Some(open) if c == open => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '/' && previous == '/' => return &line[..index - 1],
+            None => {}
+        }
+        previous = c;
+    }
+    line
+}
+
+fn paren_balance(line: &str) -> i32 {
+    let mut quote = None;
+    let mut balance = 0;
+    for c in line.chars() {
+        match quote {
+            Some(open) if c == open => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '(' => balance += 1,
+            None if c == ')' => balance -= 1,
+            None => {}
+        }
+    }
+    balance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn joins_multiline_statements_and_drops_comments() {
+        let script = r#"
+// comment
+/* include(":disabled")
+   description = "Old" */
+include(
+    ":app", // the app /* not a block
+    ":lib:core" /* inline */
+)
+maven { url = uri("https://repo.example.com") }
+include ':a',
+        ':b'
+"#;
+        let statements = statements(script);
+        assert_eq!(
+            statements,
+            vec![
+                r#"include( ":app", ":lib:core" )"#,
+                r#"maven { url = uri("https://repo.example.com") }"#,
+                "include ':a', ':b'",
+            ]
+        );
+        assert_eq!(quoted_strings(&statements[0]), vec![":app", ":lib:core"]);
+        assert_eq!(
+            assigned_string(r#"description = "Payments""#, "description"),
+            Some("Payments".to_string())
+        );
+        assert_eq!(
+            assigned_string("group 'com.example'", "group"),
+            Some("com.example".to_string())
+        );
+        assert_eq!(assigned_string("groupId = 'x'", "group"), None);
+    }
+}
@@ -0,0 +1,282 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+
+use crate::model::{InfraFile, InfraKind, NodeKind, Tree};
+
+/// YAML files larger than this are not read when looking for Kubernetes
+/// manifests.
+const MAX_MANIFEST_BYTES: u64 = 1024 * 1024;
+
+const COMPOSE_FILES: [&str; 4] = [
+    "docker-compose.yml",
+    "docker-compose.yaml",
+    "compose.yml",
+    "compose.yaml",
+];
+
+/// Finds Dockerfiles, compose files, Kubernetes manifests, Helm charts,
+/// Terraform configurations and GitHub workflows below the root and attaches
+/// each to its nearest module, or to the root when no module contains it.
+/// Compose services with a `build` context are also attached to the module
+/// they build.
+pub fn find_infra(tree: &mut Tree) {
+    if !tree.root_path.is_dir() {
+        return;
+    }
+    let modules: HashMap<PathBuf, usize> = tree
+        .nodes
+        .iter()
+        .enumerate()
+        .filter(|(_, node)| node.module.is_some())
+        .map(|(id, node)| (node.rel_path.clone(), id))
+        .collect();
+    let nearest = |path: &Path| {
+        path.ancestors()
+            .find_map(|ancestor| modules.get(ancestor).copied())
+            .unwrap_or(tree.root)
+    };
+
+    let mut found: Vec<(usize, InfraFile)> = Vec::new();
+    let mut yaml_files = Vec::new();
+    let mut chart_dirs = Vec::new();
+    let mut terraform: BTreeMap<PathBuf, u64> = BTreeMap::new();
+
+    for node in tree.nodes.iter().filter(|node| node.kind == NodeKind::File) {
+        let rel = node.rel_path.as_path();
+        let path = tree.root_path.join(rel);
+        let path = path.as_path();
+        let name = node.name.as_str();
+        let dir = rel.parent().unwrap_or(Path::new(""));
+        if is_dockerfile(name) {
+            let file = infra(InfraKind::Docker, rel, base_image(path));
+            found.push((nearest(dir), file));
+        } else if COMPOSE_FILES.contains(&name) {
+            let services = compose_services(path);
+            let names: Vec<&str> = services.iter().map(|(name, _)| name.as_str()).collect();
+            let detail = (!names.is_empty()).then(|| format!("services: {}", names.join(", ")));
+            found.push((nearest(dir), infra(InfraKind::Compose, rel, detail)));
+            for (service, context) in &services {
+                let Some(context) = context.as_deref().and_then(|c| resolve(dir, c)) else {
+                    continue;
+                };
+                let detail = Some(format!("service {service}"));
+                found.push((nearest(&context), infra(InfraKind::Compose, rel, detail)));
+            }
+        } else if name == "Chart.yaml" {
+            chart_dirs.push(dir.to_path_buf());
+        } else if name.ends_with(".tf") {
+            *terraform.entry(dir.to_path_buf()).or_default() += 1;
+        } else if name.ends_with(".yaml") || name.ends_with(".yml") {
+            yaml_files.push(rel.to_path_buf());
+        }
+    }
+
+    for chart in &chart_dirs {
+        let name = read_yaml(&tree.root_path.join(chart).join("Chart.yaml")).and_then(|value| {
+            value
+                .get("name")
+                .and_then(|n| n.as_str())
+                .map(str::to_string)
+        });
+        found.push((nearest(chart), infra(InfraKind::Helm, chart, name)));
+    }
+    for (dir, files) in terraform {
+        let unit = if files == 1 { "file" } else { "files" };
+        let detail = Some(format!("{files} {unit}"));
+        found.push((nearest(&dir), infra(InfraKind::Terraform, &dir, detail)));
+    }
+    for rel in yaml_files {
+        // Chart templates are not valid YAML until rendered, and values
+        // files are not manifests.
+        if chart_dirs.iter().any(|chart| rel.starts_with(chart)) {
+            continue;
+        }
+        if let Some(kinds) = kubernetes_kinds(&tree.root_path.join(&rel)) {
+            let dir = rel.parent().unwrap_or(Path::new(""));
+            found.push((
+                nearest(dir),
+                infra(InfraKind::Kubernetes, &rel, Some(kinds)),
+            ));
+        }
+    }
+
+    // Workflows live under a hidden directory, so they are read from disk
+    // rather than from the walk.
+    let workflows = Path::new(".github/workflows");
+    if let Ok(entries) = fs::read_dir(tree.root_path.join(workflows)) {
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if !(name.ends_with(".yml") || name.ends_with(".yaml")) {
+                continue;
+            }
+            let title = read_yaml(&entry.path()).and_then(|value| {
+                value
+                    .get("name")
+                    .and_then(|n| n.as_str())
+                    .map(str::to_string)
+            });
+            let rel = workflows.join(&name);
+            found.push((nearest(workflows), infra(InfraKind::Workflow, &rel, title)));
+        }
+    }
+
+    for (id, file) in found {
+        tree.nodes[id].infra.push(file);
+    }
+    for node in &mut tree.nodes {
+        node.infra.sort();
+        node.infra.dedup();
+    }
+}
+
+fn infra(kind: InfraKind, path: &Path, detail: Option<String>) -> InfraFile {
+    InfraFile {
+        kind,
+        path: path.to_path_buf(),
+        detail,
+    }
+}
+
+fn is_dockerfile(name: &str) -> bool {
+    matches!(name, "Dockerfile" | "Containerfile")
+        || name.starts_with("Dockerfile.")
+        || name.to_lowercase().ends_with(".dockerfile")
+}
+
+/// The image of the last `FROM` line, which is what the built image runs on.
+fn base_image(path: &Path) -> Option<String> {
+    let content = fs::read_to_string(path).ok()?;
+    content.lines().rev().find_map(|line| {
+        let mut words = line.split_whitespace();
+        let instruction = words.next()?;
+        if !instruction.eq_ignore_ascii_case("FROM") {
+            return None;
+        }
+        words
+            .find(|word| !word.starts_with("--"))
+            .map(str::to_string)
+    })
+}
+
+/// Service names in file order, with their `build` context if they have one.
+fn compose_services(path: &Path) -> Vec<(String, Option<String>)> {
+    let Some(value) = read_yaml(path) else {
+        return Vec::new();
+    };
+    let Some(services) = value.get("services").and_then(|s| s.as_mapping()) else {
+        return Vec::new();
+    };
+    services
+        .iter()
+        .filter_map(|(name, service)| {
+            let name = name.as_str()?.to_string();
+            let context = match service.get("build") {
+                Some(serde_yaml::Value::String(context)) => Some(context.clone()),
+                Some(build) => Some(
+                    build
+                        .get("context")
+                        .and_then(|c| c.as_str())
+                        .unwrap_or(".")
+                        .to_string(),
+                ),
+                None => None,
+            };
+            Some((name, context))
+        })
+        .collect()
+}
+
+/// `relative` joined onto `base`, with `.` and `..` resolved. `None` when the
+/// result leaves the root or is absolute.
+pub fn resolve(base: &Path, relative: &str) -> Option<PathBuf> {
+    let mut resolved = PathBuf::new();
+    for component in base.join(relative).components() {
+        match component {
+            Component::Normal(part) => resolved.push(part),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !resolved.pop() {
+                    return None;
+                }
+            }
+            Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    Some(resolved)
+}
+
+fn read_yaml(path: &Path) -> Option<serde_yaml::Value> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_yaml::from_str(&content).ok()
+}
+
+/// The resource kinds declared in a YAML file, in order of first appearance,
+/// when every document in it looks like a Kubernetes object.
+fn kubernetes_kinds(path: &Path) -> Option<String> {
+    let file = fs::File::open(path).ok()?;
+    let mut content = String::new();
+    file.take(MAX_MANIFEST_BYTES)
+        .read_to_string(&mut content)
+        .ok()?;
+    manifest_kinds(&content)
+}
+
+/// [`kubernetes_kinds`] for the content of a file. Empty documents and
+/// comments between `---` separators are skipped.
+fn manifest_kinds(content: &str) -> Option<String> {
+    let mut kinds: Vec<&str> = Vec::new();
+    let mut documents = 0;
+    for document in content.split("\n---") {
+        let lines: Vec<&str> = document
+            .lines()
+            .filter(|line| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+            .filter(|line| !line.starts_with("---"))
+            .collect();
+        if lines.is_empty() {
+            continue;
+        }
+        documents += 1;
+        let has_api_version = lines.iter().any(|line| line.starts_with("apiVersion:"));
+        let kind = lines.iter().find_map(|line| {
+            let kind = line.strip_prefix("kind:")?;
+            let kind = kind.trim().trim_matches(|c| c == '"' || c == '\'');
+            (!kind.is_empty()).then_some(kind)
+        });
+        let kind = kind.filter(|_| has_api_version)?;
+        if !kinds.contains(&kind) {
+            kinds.push(kind);
+        }
+    }
+    (documents > 0).then(|| kinds.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_compose_build_contexts() {
+        assert_eq!(
+            resolve(Path::new("deploy"), "../services/api"),
+            Some(PathBuf::from("services/api"))
+        );
+        assert_eq!(resolve(Path::new(""), "./web"), Some(PathBuf::from("web")));
+        assert_eq!(resolve(Path::new(""), "."), Some(PathBuf::new()));
+        assert_eq!(resolve(Path::new(""), "../elsewhere"), None);
+    }
+
+    #[test]
+    fn every_document_must_be_a_kubernetes_object() {
+        let manifest = "---\napiVersion: apps/v1\nkind: Deployment\n---\n# service\n\
+                        apiVersion: v1\nkind: Service\n---\n";
+        assert_eq!(
+            manifest_kinds(manifest).as_deref(),
+            Some("Deployment, Service")
+        );
+        let mixed = "apiVersion: v1\nkind: ConfigMap\n---\nname: not a resource\n";
+        assert_eq!(manifest_kinds(mixed), None);
+        assert_eq!(manifest_kinds("apiVersion: 2\nservices: {}\n"), None);
+    }
+}
@@ -0,0 +1,218 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+
+use crate::config::Config;
+use crate::discover::walk;
+use crate::model::{LanguageCount, NodeKind, Tree};
+
+/// Files larger than this are counted as one file with no lines, so that
+/// generated bundles and data dumps do not dominate the breakdown.
+const MAX_COUNTED_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Classifies every walked file by extension, name or shebang and rolls
+/// file and line counts per language up to every node. Directories at the
+/// depth limit are counted too, within a budget of `--max-items` entries;
+/// those cut short, and their ancestors, are marked partial.
+pub fn count_languages(tree: &mut Tree, config: &Config) {
+    if !tree.root_path.is_dir() {
+        return;
+    }
+    let mut parents = vec![None; tree.nodes.len()];
+    for (id, node) in tree.nodes.iter().enumerate() {
+        for child in &node.children {
+            parents[*child] = Some(id);
+        }
+    }
+
+    let mut counts: Vec<BTreeMap<&'static str, (u64, u64)>> =
+        vec![BTreeMap::new(); tree.nodes.len()];
+    let mut partial = vec![false; tree.nodes.len()];
+    let mut budget = config.max_items;
+    for (id, node) in tree.nodes.iter().enumerate() {
+        let path = tree.root_path.join(&node.rel_path);
+        let mut found = BTreeMap::new();
+        if node.kind == NodeKind::File {
+            count_file(&path, &mut found);
+        } else if walk::is_at_depth_limit(config, node) {
+            let complete = walk::walk_below(config, &path, &mut budget, |entry| {
+                count_file(entry.path(), &mut found);
+            });
+            if !complete {
+                let mut current = Some(id);
+                while let Some(id) = current {
+                    partial[id] = true;
+                    current = parents[id];
+                }
+            }
+        }
+        for (language, (files, lines)) in found {
+            let mut current = Some(id);
+            while let Some(id) = current {
+                let count = counts[id].entry(language).or_default();
+                count.0 += files;
+                count.1 += lines;
+                current = parents[id];
+            }
+        }
+    }
+
+    for ((node, languages), partial) in tree.nodes.iter_mut().zip(counts).zip(partial) {
+        let mut languages: Vec<LanguageCount> = languages
+            .into_iter()
+            .map(|(language, (files, lines))| LanguageCount {
+                language,
+                files,
+                lines,
+            })
+            .collect();
+        languages.sort_by(|a, b| b.lines.cmp(&a.lines).then(b.files.cmp(&a.files)));
+        node.languages = languages;
+        node.languages_partial = partial;
+    }
+}
+
+/// Adds the file at `path` to `found` if its language is known.
+fn count_file(path: &Path, found: &mut BTreeMap<&'static str, (u64, u64)>) {
+    if let Some(language) = classify(path) {
+        let count = found.entry(language).or_default();
+        count.0 += 1;
+        count.1 += count_lines(path);
+    }
+}
+
+fn count_lines(path: &Path) -> u64 {
+    let Ok(file) = fs::File::open(path) else {
+        return 0;
+    };
+    if file.metadata().map(|m| m.len()).unwrap_or(0) > MAX_COUNTED_BYTES {
+        return 0;
+    }
+    let mut reader = BufReader::new(file);
+    let mut lines = 0;
+    let mut buffer = Vec::new();
+    while let Ok(read) = reader.read_until(b'\n', &mut buffer) {
+        if read == 0 {
+            break;
+        }
+        lines += 1;
+        buffer.clear();
+    }
+    lines
+}
+
+/// The language of a file, from its extension, a well-known file name, or
+/// a `#!` line for extensionless scripts.
+pub fn classify(path: &Path) -> Option<&'static str> {
+    let name = path.file_name()?.to_str()?;
+    if let Some(language) = by_name(name) {
+        return Some(language);
+    }
+    match path.extension().and_then(|e| e.to_str()) {
+        Some(extension) => by_extension(&extension.to_ascii_lowercase()),
+        None => by_shebang(path),
+    }
+}
+
+fn by_name(name: &str) -> Option<&'static str> {
+    let language = match name {
+        "Dockerfile" | "Containerfile" => "Dockerfile",
+        "Makefile" | "GNUmakefile" => "Makefile",
+        "CMakeLists.txt" => "CMake",
+        "BUILD" | "BUILD.bazel" | "WORKSPACE" | "MODULE.bazel" => "Starlark",
+        "Gemfile" | "Rakefile" => "Ruby",
+        "Jenkinsfile" => "Groovy",
+        _ => return None,
+    };
+    Some(language)
+}
+
+fn by_extension(extension: &str) -> Option<&'static str> {
+    let language = match extension {
+        "rs" => "Rust",
+        "ts" | "tsx" | "mts" | "cts" => "TypeScript",
+        "js" | "jsx" | "mjs" | "cjs" => "JavaScript",
+        "py" | "pyi" => "Python",
+        "go" => "Go",
+        "java" => "Java",
+        "kt" | "kts" => "Kotlin",
+        "scala" => "Scala",
+        "groovy" | "gradle" => "Groovy",
+        "cs" => "C#",
+        "fs" | "fsx" | "fsi" => "F#",
+        "vb" => "Visual Basic",
+        "c" | "h" => "C",
+        "cc" | "cpp" | "cxx" | "hpp" | "hh" | "hxx" => "C++",
+        "m" | "mm" => "Objective-C",
+        "swift" => "Swift",
+        "rb" | "rake" | "gemspec" => "Ruby",
+        "php" => "PHP",
+        "dart" => "Dart",
+        "lua" => "Lua",
+        "ex" | "exs" => "Elixir",
+        "erl" | "hrl" => "Erlang",
+        "hs" => "Haskell",
+        "zig" => "Zig",
+        "pl" | "pm" => "Perl",
+        "r" => "R",
+        "sh" | "bash" | "zsh" => "Shell",
+        "ps1" | "psm1" => "PowerShell",
+        "sql" => "SQL",
+        "proto" => "Protocol Buffers",
+        "tf" | "hcl" => "HCL",
+        "bzl" | "bazel" => "Starlark",
+        "vue" => "Vue",
+        "svelte" => "Svelte",
+        "html" | "htm" => "HTML",
+        "css" | "scss" | "sass" | "less" => "CSS",
+        "toml" => "TOML",
+        "yaml" | "yml" => "YAML",
+        "json" | "jsonc" => "JSON",
+        "xml" | "csproj" | "fsproj" | "vbproj" | "props" | "targets" => "XML",
+        "md" | "markdown" | "mdx" => "Markdown",
+        "rst" => "reStructuredText",
+        _ => return None,
+    };
+    Some(language)
+}
+
+fn by_shebang(path: &Path) -> Option<&'static str> {
+    let mut head = [0u8; 128];
+    let read = fs::File::open(path).ok()?.read(&mut head).ok()?;
+    let first = std::str::from_utf8(&head[..read]).ok()?.lines().next()?;
+    let command = first.strip_prefix("#!")?;
+    let mut words = command.split_whitespace();
+    let mut program = words.next()?.rsplit('/').next()?;
+    if program == "env" {
+        program = words.find(|word| !word.starts_with('-'))?;
+    }
+    let language = match program.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.') {
+        "python" => "Python",
+        "node" | "deno" | "bun" => "JavaScript",
+        "sh" | "bash" | "zsh" | "dash" | "ksh" => "Shell",
+        "ruby" => "Ruby",
+        "perl" => "Perl",
+        "php" => "PHP",
+        _ => return None,
+    };
+    Some(language)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_by_extension_name_and_shebang() {
+        assert_eq!(classify(Path::new("src/lib.rs")), Some("Rust"));
+        assert_eq!(classify(Path::new("web/App.TSX")), Some("TypeScript"));
+        assert_eq!(classify(Path::new("Dockerfile")), Some("Dockerfile"));
+        assert_eq!(classify(Path::new("logo.png")), None);
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let script = dir.path().join("deploy");
+        fs::write(&script, "#!/usr/bin/env python3\nprint('hi')\n").expect("write");
+        assert_eq!(classify(&script), Some("Python"));
+    }
+}
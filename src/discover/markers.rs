@@ -1,8 +1,11 @@
+use std::path::Path;
+
 use anyhow::Result;
 
 use crate::config::Config;
-use crate::discover::summary;
-use crate::model::{ModuleInfo, ModuleKind, NodeKind, Tree, WorkspaceResolved};
+use crate::discover::workspace::PackageRootMatcher;
+use crate::discover::{bazel, ruby, summary};
+use crate::model::{ModuleInfo, ModuleKind, NodeKind, Tree, WorkspaceKind};
 
 #[derive(Debug, Clone)]
 pub struct ModuleCandidate {
@@ -11,15 +14,23 @@ pub struct ModuleCandidate {
     pub markers: Vec<String>,
 }
 
+/// MSBuild project files for C#, F# and Visual Basic.
+pub const DOTNET_PROJECT_EXTENSIONS: [&str; 3] = [".csproj", ".fsproj", ".vbproj"];
+
 fn marker_kind_for_file(name: &str) -> Option<ModuleKind> {
     match name {
         "package.json" => Some(ModuleKind::Node),
         "pyproject.toml" | "setup.py" | "setup.cfg" => Some(ModuleKind::Python),
         "Cargo.toml" => Some(ModuleKind::Rust),
         "go.mod" => Some(ModuleKind::Go),
+        "Gemfile" => Some(ModuleKind::Ruby),
         "pom.xml" | "build.gradle" | "build.gradle.kts" => Some(ModuleKind::Java),
+        _ if ruby::is_gemspec(name) => Some(ModuleKind::Ruby),
         _ => {
-            if name.ends_with(".csproj") {
+            if DOTNET_PROJECT_EXTENSIONS
+                .iter()
+                .any(|extension| name.ends_with(extension))
+            {
                 Some(ModuleKind::DotNet)
             } else {
                 None
@@ -28,42 +39,91 @@ fn marker_kind_for_file(name: &str) -> Option<ModuleKind> {
     }
 }
 
+/// Whether `name` marks a Bazel package or workspace root.
+fn is_bazel_marker(name: &str) -> bool {
+    bazel::BUILD_FILES.contains(&name) || bazel::WORKSPACE_FILES.contains(&name)
+}
+
+/// Lower wins when a directory has markers of several kinds. A BUILD file
+/// takes precedence since in a Bazel repo it, not the language manifest,
+/// defines the package boundary. A bare `Gemfile` comes last: React Native
+/// apps and many other projects carry one just for tooling.
 fn kind_priority(kind: &ModuleKind) -> u8 {
     match kind {
-        ModuleKind::Node => 0,
-        ModuleKind::Python => 1,
-        ModuleKind::Rust => 2,
-        ModuleKind::Go => 3,
-        ModuleKind::Java => 4,
-        ModuleKind::DotNet => 5,
-        ModuleKind::Unknown => 6,
+        ModuleKind::Bazel => 0,
+        ModuleKind::Node => 1,
+        ModuleKind::Python => 2,
+        ModuleKind::Rust => 3,
+        ModuleKind::Go => 4,
+        ModuleKind::Java => 5,
+        ModuleKind::DotNet => 6,
+        ModuleKind::Ruby => 7,
+        ModuleKind::Unknown => 8,
+    }
+}
+
+/// Picks the module kind and marker list for the directory at `dir` from the
+/// names of the files directly inside it, or `None` when none of them is a
+/// marker. A gem or Rails app is Ruby even when it also has a `package.json`
+/// for its front-end assets. Bazel files are markers only in a Bazel
+/// workspace, so that a stray `BUILD` file elsewhere does not hide the
+/// language manifest next to it.
+pub fn classify_markers<'a, I>(
+    dir: &Path,
+    file_names: I,
+    workspace: Option<WorkspaceKind>,
+) -> Option<(ModuleKind, Vec<String>)>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let in_bazel = workspace == Some(WorkspaceKind::Bazel);
+    let mut markers = Vec::new();
+    let mut kinds = Vec::new();
+    for name in file_names {
+        let kind = if in_bazel && is_bazel_marker(name) {
+            Some(ModuleKind::Bazel)
+        } else {
+            marker_kind_for_file(name)
+        };
+        if let Some(kind) = kind {
+            markers.push(name.to_string());
+            kinds.push(kind);
+        }
+    }
+
+    if markers.is_empty() {
+        return None;
     }
+    let is_ruby_project = kinds.contains(&ModuleKind::Ruby)
+        && (markers.iter().any(|name| ruby::is_gemspec(name)) || ruby::is_rails_app(dir));
+    if is_ruby_project {
+        return Some((ModuleKind::Ruby, markers));
+    }
+    let kind = kinds
+        .into_iter()
+        .min_by_key(kind_priority)
+        .unwrap_or(ModuleKind::Unknown);
+    Some((kind, markers))
 }
 
-pub fn collect_module_candidates(tree: &Tree) -> Vec<ModuleCandidate> {
+pub fn collect_module_candidates(
+    tree: &Tree,
+    workspace: Option<WorkspaceKind>,
+) -> Vec<ModuleCandidate> {
     let mut candidates = Vec::new();
     for (node_id, node) in tree.nodes.iter().enumerate() {
         if node.kind != NodeKind::Dir {
             continue;
         }
-        let mut markers = Vec::new();
-        let mut kinds = Vec::new();
-        for child_id in &node.children {
-            let child = &tree.nodes[*child_id];
-            if child.kind == NodeKind::Dir {
-                continue;
-            }
-            if let Some(kind) = marker_kind_for_file(&child.name) {
-                markers.push(child.name.clone());
-                kinds.push(kind);
-            }
-        }
+        let file_names = node
+            .children
+            .iter()
+            .map(|child_id| &tree.nodes[*child_id])
+            .filter(|child| child.kind != NodeKind::Dir)
+            .map(|child| child.name.as_str());
 
-        if !markers.is_empty() {
-            let kind = kinds
-                .into_iter()
-                .min_by_key(kind_priority)
-                .unwrap_or(ModuleKind::Unknown);
+        let dir = tree.root_path.join(&node.rel_path);
+        if let Some((kind, markers)) = classify_markers(&dir, file_names, workspace) {
             candidates.push(ModuleCandidate {
                 node_id,
                 kind,
@@ -74,33 +134,30 @@ pub fn collect_module_candidates(tree: &Tree) -> Vec<ModuleCandidate> {
     candidates
 }
 
-fn is_within_any(path: &std::path::Path, roots: &[std::path::PathBuf]) -> bool {
-    roots.iter().any(|root| path.starts_with(root))
-}
-
+/// Turns candidates into modules. Inside a workspace, candidates other than
+/// the root are kept only when `matcher` places them in a package.
 pub fn annotate_modules(
     tree: &mut Tree,
     candidates: &[ModuleCandidate],
-    workspace: Option<&WorkspaceResolved>,
+    matcher: Option<&PackageRootMatcher>,
     _config: &Config,
 ) -> Result<()> {
-    let package_roots = workspace.map(|w| w.package_roots.as_slice()).unwrap_or(&[]);
-    let restrict_to_workspace = !package_roots.is_empty();
-
     for candidate in candidates {
         let rel = tree.nodes[candidate.node_id].rel_path.clone();
         let is_root = rel.as_os_str().is_empty();
-        if restrict_to_workspace && !is_root && !is_within_any(&rel, package_roots) {
-            continue;
+        if let Some(matcher) = matcher {
+            if !is_root && !matcher.is_within_package(&rel, &candidate.markers) {
+                continue;
+            }
         }
 
         let module_path = tree.root_path.join(&rel);
         let summary = summary::read_summary(&module_path, candidate.kind);
-        tree.nodes[candidate.node_id].module = Some(ModuleInfo {
-            kind: candidate.kind,
+        tree.nodes[candidate.node_id].module = Some(ModuleInfo::new(
+            candidate.kind,
             summary,
-            markers: candidate.markers.clone(),
-        });
+            candidate.markers.clone(),
+        ));
     }
 
     Ok(())
@@ -113,22 +170,19 @@ mod tests {
 
     #[test]
     fn detects_node_module_marker() {
-        let root = Node {
-            name: "root".to_string(),
-            rel_path: std::path::PathBuf::new(),
-            kind: NodeKind::Dir,
-            children: vec![1],
-            module: None,
-            error: None,
-        };
-        let pkg = Node {
-            name: "package.json".to_string(),
-            rel_path: std::path::PathBuf::from("package.json"),
-            kind: NodeKind::File,
-            children: vec![],
-            module: None,
-            error: None,
-        };
+        let mut root = Node::new(
+            "root".to_string(),
+            std::path::PathBuf::new(),
+            NodeKind::Dir,
+            None,
+        );
+        root.children = vec![1];
+        let pkg = Node::new(
+            "package.json".to_string(),
+            std::path::PathBuf::from("package.json"),
+            NodeKind::File,
+            None,
+        );
         let tree = Tree {
             root_path: std::path::PathBuf::new(),
             root: 0,
@@ -137,9 +191,26 @@ mod tests {
             truncated_at: 0,
         };
 
-        let candidates = collect_module_candidates(&tree);
+        let candidates = collect_module_candidates(&tree, None);
         assert_eq!(candidates.len(), 1);
         assert_eq!(candidates[0].kind, ModuleKind::Node);
         assert_eq!(candidates[0].markers, vec!["package.json".to_string()]);
     }
+
+    #[test]
+    fn build_files_mark_bazel_packages_only_in_bazel_workspaces() {
+        let files = ["BUILD", "package.json"];
+        let dir = std::path::Path::new("");
+        assert_eq!(
+            classify_markers(dir, files, None),
+            Some((ModuleKind::Node, vec!["package.json".to_string()]))
+        );
+        assert_eq!(
+            classify_markers(dir, files, Some(WorkspaceKind::Bazel)),
+            Some((
+                ModuleKind::Bazel,
+                vec!["BUILD".to_string(), "package.json".to_string()]
+            ))
+        );
+    }
 }
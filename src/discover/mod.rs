@@ -1,12 +1,25 @@
+pub mod bazel;
+pub mod changes;
+pub mod churn;
+pub mod deps;
+pub mod docs;
+pub mod entrypoints;
+pub mod git;
+pub mod gradle;
+pub mod infra;
+pub mod languages;
 pub mod markers;
+pub mod owners;
+pub mod ruby;
 pub mod summary;
+pub mod test_suites;
 pub mod walk;
 pub mod workspace;
 
 use anyhow::Result;
 
 use crate::config::Config;
-use crate::model::{Tree, WorkspaceResolved};
+use crate::model::{Lens, Tree, WorkspaceResolved};
 
 #[derive(Debug)]
 pub struct DiscoverResult {
@@ -27,24 +40,50 @@ pub fn discover(config: &Config) -> Result<DiscoverResult> {
     };
 
     let workspace_info = workspace::detect_workspace(&workspace_root)?;
-    let module_candidates = markers::collect_module_candidates(&tree);
+    let module_candidates =
+        markers::collect_module_candidates(&tree, workspace_info.as_ref().map(|info| info.kind));
 
-    let workspace_resolved = if let Some(info) = workspace_info {
+    let (workspace_resolved, matcher) = if let Some(info) = workspace_info {
         let package_roots = workspace::resolve_package_roots(&tree, &info, &module_candidates)?;
-        Some(WorkspaceResolved {
+        let matcher = workspace::PackageRootMatcher::new(&info, &workspace_root)?;
+        let resolved = WorkspaceResolved {
             kind: info.kind,
             package_roots,
-        })
+        };
+        (Some(resolved), Some(matcher))
     } else {
-        None
+        (None, None)
     };
 
-    markers::annotate_modules(
-        &mut tree,
-        &module_candidates,
-        workspace_resolved.as_ref(),
-        config,
-    )?;
+    markers::annotate_modules(&mut tree, &module_candidates, matcher.as_ref(), config)?;
+
+    if config.lens == Lens::Deps {
+        deps::resolve_dependencies(&mut tree);
+    }
+    if config.lens == Lens::Owners {
+        owners::assign_owners(&mut tree);
+    }
+    if config.lens == Lens::Churn {
+        churn::assign_churn(&mut tree, &config.since)?;
+    }
+    if config.lens == Lens::Entrypoints {
+        entrypoints::find_entrypoints(&mut tree);
+    }
+    if config.lens == Lens::Languages {
+        languages::count_languages(&mut tree, config);
+    }
+    if config.lens == Lens::Tests {
+        test_suites::find_tests(&mut tree, config);
+    }
+    if config.lens == Lens::Docs {
+        docs::find_docs(&mut tree);
+    }
+    if config.lens == Lens::Infra {
+        infra::find_infra(&mut tree);
+    }
+    if let Some(base) = &config.changed_since {
+        changes::mark_affected(&mut tree, base, config.include_dependents)?;
+    }
 
     Ok(DiscoverResult {
         tree,
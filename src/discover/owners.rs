@@ -0,0 +1,125 @@
+use std::fs;
+use std::path::Path;
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+
+use crate::model::Tree;
+
+/// Where GitHub looks for a CODEOWNERS file, in the order it looks.
+const CODEOWNERS_LOCATIONS: [&str; 3] = [".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+struct Rule {
+    matcher: Gitignore,
+    owners: Vec<String>,
+}
+
+/// Parsed CODEOWNERS rules. As on GitHub, the last matching rule wins, and a
+/// rule without owners leaves matching paths unowned.
+pub struct CodeOwners {
+    rules: Vec<Rule>,
+}
+
+impl CodeOwners {
+    /// Reads the first CODEOWNERS file found under `root`, if any.
+    pub fn load(root: &Path) -> Option<CodeOwners> {
+        CODEOWNERS_LOCATIONS.iter().find_map(|location| {
+            let content = fs::read_to_string(root.join(location)).ok()?;
+            Some(CodeOwners::parse(root, &content))
+        })
+    }
+
+    pub fn parse(root: &Path, content: &str) -> CodeOwners {
+        let mut rules = Vec::new();
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.split_whitespace();
+            let Some(pattern) = fields.next() else {
+                continue;
+            };
+            let owners: Vec<String> = fields
+                .take_while(|field| !field.starts_with('#'))
+                .map(str::to_string)
+                .collect();
+
+            // Patterns use gitignore syntax; lines that fail to parse are
+            // skipped, as GitHub does.
+            let mut builder = GitignoreBuilder::new(root);
+            if builder.add_line(None, pattern).is_err() {
+                continue;
+            }
+            let Ok(matcher) = builder.build() else {
+                continue;
+            };
+            rules.push(Rule { matcher, owners });
+        }
+        CodeOwners { rules }
+    }
+
+    /// Owners of a path relative to the root. A rule matches a path when it
+    /// matches the path itself or any of its parent directories.
+    pub fn owners_of(&self, rel_path: &Path, is_dir: bool) -> &[String] {
+        if rel_path.as_os_str().is_empty() {
+            return self.owners_of(Path::new("."), true);
+        }
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| {
+                rule.matcher
+                    .matched_path_or_any_parents(rel_path, is_dir)
+                    .is_ignore()
+            })
+            .map(|rule| rule.owners.as_slice())
+            .unwrap_or(&[])
+    }
+}
+
+/// Attaches CODEOWNERS owners to every node in the tree. Nodes stay without
+/// owners when the repository has no CODEOWNERS file.
+pub fn assign_owners(tree: &mut Tree) {
+    let Some(codeowners) = CodeOwners::load(&tree.root_path) else {
+        return;
+    };
+    for node in &mut tree.nodes {
+        node.owners = codeowners.owners_of(&node.rel_path, node.is_dir()).to_vec();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn last_matching_rule_wins() {
+        let codeowners = CodeOwners::parse(
+            Path::new("/repo"),
+            "# comment\n\
+             *                 @acme/platform\n\
+             /packages/web/    @acme/web @alice # trailing\n\
+             *.md              @acme/docs\n\
+             /packages/legacy/\n",
+        );
+        assert_eq!(
+            codeowners.owners_of(Path::new("packages/web/src/index.ts"), false),
+            ["@acme/web", "@alice"]
+        );
+        assert_eq!(
+            codeowners.owners_of(Path::new("packages/web/README.md"), false),
+            ["@acme/docs"]
+        );
+        assert_eq!(
+            codeowners.owners_of(Path::new("packages/api"), true),
+            ["@acme/platform"]
+        );
+        assert!(codeowners
+            .owners_of(Path::new("packages/legacy"), true)
+            .is_empty());
+        assert_eq!(
+            codeowners.owners_of(Path::new(""), true),
+            ["@acme/platform"]
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directories whose children are Rails engines in a componentised app.
+pub const ENGINE_DIRS: [&str; 2] = ["engines", "components"];
+
+/// Whether `name` is a gemspec file.
+pub fn is_gemspec(name: &str) -> bool {
+    name.ends_with(".gemspec")
+}
+
+/// The first gemspec directly inside `dir`, by name.
+pub fn find_gemspec(dir: &Path) -> Option<PathBuf> {
+    let mut gemspecs: Vec<PathBuf> = fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .is_some_and(|name| is_gemspec(&name.to_string_lossy()))
+        })
+        .collect();
+    gemspecs.sort();
+    gemspecs.into_iter().next()
+}
+
+/// The `name` and `summary` a gemspec assigns, e.g. from
+/// `spec.name = "billing"`.
+pub fn gemspec_fields(content: &str) -> (Option<String>, Option<String>) {
+    let mut name = None;
+    let mut summary = None;
+    for line in content.lines() {
+        name = name.or_else(|| spec_string(line, "name"));
+        summary = summary.or_else(|| spec_string(line, "summary"));
+    }
+    (name, summary)
+}
+
+/// Whether `module_path` is a Rails application.
+pub fn is_rails_app(module_path: &Path) -> bool {
+    module_path.join("config/application.rb").is_file()
+}
+
+/// The Ruby module that holds the `Rails::Application` subclass in
+/// `config/application.rb`, which is the application's name.
+pub fn rails_application(module_path: &Path) -> Option<String> {
+    let content = fs::read_to_string(module_path.join("config/application.rb")).ok()?;
+    let mut module = None;
+    for line in content.lines() {
+        let line = line.trim();
+        if let Some(name) = line.strip_prefix("module ") {
+            module = Some(name.trim().to_string());
+        } else if line.starts_with("class Application") && line.contains("Rails::Application") {
+            return module;
+        }
+    }
+    None
+}
+
+/// The string in an assignment such as `spec.summary = "..."` or
+/// `s.summary = '...'.freeze`.
+fn spec_string(line: &str, field: &str) -> Option<String> {
+    let (target, value) = line.split_once('=')?;
+    if !target.trim().ends_with(&format!(".{field}")) {
+        return None;
+    }
+    let value = value.trim();
+    let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let rest = &value[1..];
+    let end = rest.find(quote)?;
+    Some(rest[..end].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_gemspec_name_and_summary() {
+        let gemspec = r#"
+require_relative "lib/billing/version"
+
+Gem::Specification.new do |spec|
+  spec.name        = "billing"
+  spec.version     = Billing::VERSION
+  spec.summary     = 'Invoices and payments'.freeze
+  spec.metadata["homepage_uri"] = spec.homepage
+  spec.add_dependency "rails", ">= 7.1"
+end
+"#;
+        assert_eq!(
+            gemspec_fields(gemspec),
+            (
+                Some("billing".to_string()),
+                Some("Invoices and payments".to_string())
+            )
+        );
+    }
+}
@@ -1,7 +1,10 @@
+use std::collections::HashMap;
 use std::fs;
 use std::io::Read;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use crate::discover::markers::DOTNET_PROJECT_EXTENSIONS;
+use crate::discover::{bazel, gradle, ruby};
 use crate::model::ModuleKind;
 
 pub fn read_summary(module_path: &Path, kind: ModuleKind) -> Option<String> {
@@ -10,6 +13,10 @@ pub fn read_summary(module_path: &Path, kind: ModuleKind) -> Option<String> {
         ModuleKind::Rust => read_cargo_toml(module_path),
         ModuleKind::Python => read_pyproject(module_path).or_else(|| read_setup_cfg(module_path)),
         ModuleKind::Go => read_go_mod(module_path),
+        ModuleKind::Java => read_pom(module_path).or_else(|| read_gradle_build(module_path)),
+        ModuleKind::DotNet => read_dotnet_project(module_path),
+        ModuleKind::Bazel => read_bazel_package(module_path),
+        ModuleKind::Ruby => read_gemspec(module_path).or_else(|| read_rails_app(module_path)),
         _ => None,
     };
     summary.or_else(|| read_readme_line(module_path))
@@ -103,6 +110,260 @@ fn read_pyproject(module_path: &Path) -> Option<String> {
     None
 }
 
+/// How many parent poms are followed when inheriting a description.
+const MAX_POM_PARENTS: usize = 8;
+
+/// The fields of a pom that summaries use.
+struct Pom {
+    name: Option<String>,
+    artifact_id: Option<String>,
+    description: Option<String>,
+    /// The `<parent>` artifactId and the pom file it resolves to on disk, if
+    /// it is local.
+    parent: Option<(Option<String>, PathBuf)>,
+}
+
+fn parse_pom(path: &Path) -> Option<Pom> {
+    let content = fs::read_to_string(path).ok()?;
+    let doc = roxmltree::Document::parse(&content).ok()?;
+    let project = doc.root_element();
+    let parent = project
+        .children()
+        .find(|node| node.has_tag_name("parent"))
+        .and_then(|parent| {
+            // An empty `<relativePath/>` means the parent only lives in a
+            // repository.
+            let relative = match parent.children().find(|n| n.has_tag_name("relativePath")) {
+                Some(node) => node.text().map(str::trim).filter(|t| !t.is_empty())?,
+                None => "../pom.xml",
+            };
+            let mut parent_path = path.parent()?.join(relative);
+            if parent_path.is_dir() {
+                parent_path.push("pom.xml");
+            }
+            Some((xml_child_text(parent, "artifactId"), parent_path))
+        });
+    Some(Pom {
+        // Placeholders such as `${project.artifactId}` are not worth
+        // interpolating; the artifactId says the same.
+        name: xml_child_text(project, "name").filter(|name| !name.contains("${")),
+        artifact_id: xml_child_text(project, "artifactId"),
+        description: xml_child_text(project, "description"),
+        parent,
+    })
+}
+
+/// The trimmed text of a direct child element, with runs of whitespace
+/// collapsed so that wrapped descriptions fit on one line.
+fn xml_child_text(node: roxmltree::Node, tag: &str) -> Option<String> {
+    let text = node.children().find(|n| n.has_tag_name(tag))?.text()?;
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!text.is_empty()).then_some(text)
+}
+
+/// `<name>` (or `<artifactId>`) and `<description>` from `pom.xml`. A missing
+/// description is inherited from local parent poms, as Maven does.
+fn read_pom(module_path: &Path) -> Option<String> {
+    let pom = parse_pom(&module_path.join("pom.xml"))?;
+    let name = pom.name.or(pom.artifact_id);
+    let mut desc = pom.description;
+    let mut parent = pom.parent;
+    for _ in 0..MAX_POM_PARENTS {
+        if desc.is_some() {
+            break;
+        }
+        let Some((artifact_id, path)) = parent else {
+            break;
+        };
+        let Some(parent_pom) = parse_pom(&path) else {
+            break;
+        };
+        // A relative path pointing at an unrelated pom is not the parent.
+        if artifact_id.is_some() && parent_pom.artifact_id != artifact_id {
+            break;
+        }
+        desc = parent_pom.description;
+        parent = parent_pom.parent;
+    }
+
+    match (name, desc) {
+        (Some(name), Some(desc)) => Some(format!("{name} - {desc}")),
+        (Some(name), None) => Some(name),
+        (None, Some(desc)) => Some(desc),
+        _ => None,
+    }
+}
+
+/// `group` and `description` from `build.gradle(.kts)`.
+fn read_gradle_build(module_path: &Path) -> Option<String> {
+    let content = gradle::BUILD_FILES
+        .iter()
+        .find_map(|name| fs::read_to_string(module_path.join(name)).ok())?;
+    let mut group = None;
+    let mut desc = None;
+    for statement in gradle::statements(&content) {
+        group = group.or_else(|| gradle::assigned_string(&statement, "group"));
+        desc = desc.or_else(|| gradle::assigned_string(&statement, "description"));
+    }
+
+    match (group, desc) {
+        (Some(group), Some(desc)) => Some(format!("{group} - {desc}")),
+        (Some(group), None) => Some(group),
+        (None, Some(desc)) => Some(desc),
+        _ => None,
+    }
+}
+
+fn read_gemspec(module_path: &Path) -> Option<String> {
+    let content = fs::read_to_string(ruby::find_gemspec(module_path)?).ok()?;
+    match ruby::gemspec_fields(&content) {
+        (Some(name), Some(summary)) => Some(format!("{name} - {summary}")),
+        (Some(name), None) => Some(name),
+        (None, Some(summary)) => Some(summary),
+        _ => None,
+    }
+}
+
+/// `Shop (Rails app)` for an application whose `config/application.rb`
+/// defines `Shop::Application`.
+fn read_rails_app(module_path: &Path) -> Option<String> {
+    ruby::rails_application(module_path).map(|name| format!("{name} (Rails app)"))
+}
+
+/// How many targets a Bazel summary lists before eliding the rest.
+const MAX_BAZEL_TARGETS: usize = 4;
+
+/// The declared targets of a Bazel package, e.g. `server (go_library),
+/// cli (go_binary), +2 more`, after the module name from `MODULE.bazel` at a
+/// workspace root.
+fn read_bazel_package(module_path: &Path) -> Option<String> {
+    let targets = bazel::targets(module_path);
+    let mut listed: Vec<String> = targets
+        .iter()
+        .take(MAX_BAZEL_TARGETS)
+        .map(|(rule, name)| format!("{name} ({rule})"))
+        .collect();
+    if targets.len() > MAX_BAZEL_TARGETS {
+        listed.push(format!("+{} more", targets.len() - MAX_BAZEL_TARGETS));
+    }
+    let targets = (!listed.is_empty()).then(|| listed.join(", "));
+
+    match (bazel::module_name(module_path), targets) {
+        (Some(name), Some(targets)) => Some(format!("{name} - {targets}")),
+        (Some(name), None) => Some(name),
+        (None, Some(targets)) => Some(targets),
+        _ => None,
+    }
+}
+
+/// MSBuild properties that summaries use.
+const MSBUILD_PROPERTIES: [&str; 5] = [
+    "AssemblyName",
+    "Description",
+    "TargetFramework",
+    "TargetFrameworks",
+    "OutputType",
+];
+
+/// `AssemblyName - Description (frameworks, OutputType)` from the module's
+/// `.csproj`, `.fsproj` or `.vbproj`. As in MSBuild, the nearest
+/// `Directory.Build.props` supplies defaults that the project can override.
+fn read_dotnet_project(module_path: &Path) -> Option<String> {
+    let Ok(entries) = fs::read_dir(module_path) else {
+        return None;
+    };
+    let mut projects: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy())
+                .unwrap_or_default();
+            DOTNET_PROJECT_EXTENSIONS
+                .iter()
+                .any(|extension| name.ends_with(extension))
+        })
+        .collect();
+    projects.sort();
+    let project = projects.first()?;
+
+    let mut properties = HashMap::new();
+    if let Some(props) = module_path
+        .ancestors()
+        .map(|dir| dir.join("Directory.Build.props"))
+        .find(|path| path.is_file())
+    {
+        read_msbuild_properties(&props, &mut properties);
+    }
+    read_msbuild_properties(project, &mut properties);
+
+    let name = properties.remove("AssemblyName").or_else(|| {
+        project
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+    });
+    let mut details: Vec<String> = properties
+        .remove("TargetFrameworks")
+        .or_else(|| properties.remove("TargetFramework"))
+        .map(|frameworks| {
+            frameworks
+                .split(';')
+                .map(str::trim)
+                .filter(|f| !f.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    details.extend(properties.remove("OutputType"));
+
+    let mut summary = match (name, properties.remove("Description")) {
+        (Some(name), Some(desc)) => format!("{name} - {desc}"),
+        (Some(name), None) => name,
+        (None, Some(desc)) => desc,
+        _ => return None,
+    };
+    if !details.is_empty() {
+        summary.push_str(&format!(" ({})", details.join(", ")));
+    }
+    Some(summary)
+}
+
+/// Unconditional `<PropertyGroup>` values from an MSBuild file, later ones
+/// overriding earlier ones. Values that need evaluation (`$(...)`) are
+/// skipped.
+fn read_msbuild_properties(path: &Path, properties: &mut HashMap<&'static str, String>) {
+    let Ok(content) = fs::read_to_string(path) else {
+        return;
+    };
+    let Ok(doc) = roxmltree::Document::parse(&content) else {
+        return;
+    };
+    for group in doc
+        .root_element()
+        .children()
+        .filter(|node| node.has_tag_name("PropertyGroup") && node.attribute("Condition").is_none())
+    {
+        for property in group
+            .children()
+            .filter(|n| n.attribute("Condition").is_none())
+        {
+            let Some(key) = MSBUILD_PROPERTIES
+                .iter()
+                .find(|key| property.has_tag_name(**key))
+            else {
+                continue;
+            };
+            let Some(value) = property.text().map(str::trim) else {
+                continue;
+            };
+            if !value.is_empty() && !value.contains("$(") {
+                properties.insert(key, value.to_string());
+            }
+        }
+    }
+}
+
 fn read_setup_cfg(module_path: &Path) -> Option<String> {
     let path = module_path.join("setup.cfg");
     let content = fs::read_to_string(path).ok()?;
@@ -154,8 +415,11 @@ fn read_go_mod(module_path: &Path) -> Option<String> {
     None
 }
 
-fn read_readme_line(module_path: &Path) -> Option<String> {
-    let entries = fs::read_dir(module_path).ok()?;
+/// README-like files directly inside `module_path`, sorted by name.
+pub fn readme_candidates(module_path: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(module_path) else {
+        return Vec::new();
+    };
     let mut readmes: Vec<String> = entries
         .filter_map(|entry| entry.ok())
         .filter_map(|entry| {
@@ -169,7 +433,49 @@ fn read_readme_line(module_path: &Path) -> Option<String> {
         .collect();
 
     readmes.sort();
-    for name in readmes {
+    readmes
+}
+
+/// The first Markdown heading of the README at `path`, e.g. `Payments API`
+/// for `# Payments API` or for a line underlined with `===` or `---`. Badges
+/// and prose before it are skipped, as are fenced code blocks.
+pub fn readme_title(path: &Path) -> Option<String> {
+    heading_title(&read_head(path)?)
+}
+
+fn heading_title(content: &str) -> Option<String> {
+    let mut in_fence = false;
+    let mut previous: Option<&str> = None;
+    for line in content.lines().map(str::trim) {
+        if line.starts_with("```") || line.starts_with("~~~") {
+            in_fence = !in_fence;
+            previous = None;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix('#') {
+            let text = rest.trim_start_matches('#');
+            if text.is_empty() || text.starts_with(' ') {
+                let title = text.trim().trim_end_matches('#').trim();
+                if !title.is_empty() {
+                    return Some(title.to_string());
+                }
+            }
+        }
+        let underline =
+            !line.is_empty() && (line.chars().all(|c| c == '=') || line.chars().all(|c| c == '-'));
+        if let Some(title) = previous.filter(|_| underline) {
+            return Some(title.to_string());
+        }
+        previous = (!line.is_empty()).then_some(line);
+    }
+    None
+}
+
+fn read_readme_line(module_path: &Path) -> Option<String> {
+    for name in readme_candidates(module_path) {
         let path = module_path.join(&name);
         if let Some(line) = read_first_line(&path) {
             return Some(line);
@@ -179,10 +485,7 @@ fn read_readme_line(module_path: &Path) -> Option<String> {
 }
 
 fn read_first_line(path: &Path) -> Option<String> {
-    let mut file = fs::File::open(path).ok()?;
-    let mut buffer = Vec::new();
-    let _ = file.by_ref().take(4096).read_to_end(&mut buffer).ok()?;
-    let content = String::from_utf8_lossy(&buffer);
+    let content = read_head(path)?;
     for line in content.lines() {
         let trimmed = line.trim();
         if !trimmed.is_empty() {
@@ -191,3 +494,28 @@ fn read_first_line(path: &Path) -> Option<String> {
     }
     None
 }
+
+/// The first 4 KiB of the file at `path`.
+fn read_head(path: &Path) -> Option<String> {
+    let mut file = fs::File::open(path).ok()?;
+    let mut buffer = Vec::new();
+    file.by_ref().take(4096).read_to_end(&mut buffer).ok()?;
+    Some(String::from_utf8_lossy(&buffer).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn readme_title_skips_badges_and_code() {
+        let readme = "[![CI](https://ci.example/badge.svg)](https://ci.example)\n\n\
+                      ```sh\n# install\n```\n\n## Payments API ##\n";
+        assert_eq!(heading_title(readme).as_deref(), Some("Payments API"));
+        assert_eq!(
+            heading_title("<img src=\"logo.png\">\n\nLedger\n======\n").as_deref(),
+            Some("Ledger")
+        );
+        assert_eq!(heading_title("#hashtag\nJust prose.\n\n---\n"), None);
+    }
+}
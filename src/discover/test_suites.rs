@@ -0,0 +1,186 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::discover::walk;
+use crate::model::{NodeKind, TestInfo, Tree};
+
+/// Directory names whose contents are all tests.
+const TEST_DIRS: [&str; 4] = ["tests", "test", "__tests__", "spec"];
+
+/// Only this much of a Rust source is searched for `#[cfg(test)]`.
+const MAX_SCANNED_BYTES: u64 = 1024 * 1024;
+
+/// Extensions that take `.spec` / `.test` infixes (`app.spec.ts`).
+const JS_EXTENSIONS: [&str; 8] = ["ts", "tsx", "mts", "cts", "js", "jsx", "mjs", "cjs"];
+
+/// Test files found so far for one module.
+#[derive(Default)]
+struct Found {
+    files: u64,
+    locations: BTreeSet<String>,
+    partial: bool,
+}
+
+/// Finds test files below every annotated module and records how many there
+/// are and where they live. Each file counts towards its nearest module only,
+/// so nested packages do not inflate their parents. Directories at the depth
+/// limit are searched too, within a budget of `--max-items` entries.
+pub fn find_tests(tree: &mut Tree, config: &Config) {
+    let mut found: HashMap<PathBuf, Found> = tree
+        .nodes
+        .iter()
+        .filter(|node| node.module.is_some())
+        .map(|node| (node.rel_path.clone(), Found::default()))
+        .collect();
+
+    if tree.root_path.is_dir() {
+        let mut budget = config.max_items;
+        for node in &tree.nodes {
+            if node.kind == NodeKind::File {
+                record_file(&tree.root_path, &node.rel_path, &mut found);
+            } else if walk::is_at_depth_limit(config, node) {
+                let dir = tree.root_path.join(&node.rel_path);
+                let complete = walk::walk_below(config, &dir, &mut budget, |entry| {
+                    if let Ok(rel) = entry.path().strip_prefix(&tree.root_path) {
+                        record_file(&tree.root_path, rel, &mut found);
+                    }
+                });
+                if !complete {
+                    if let Some(stats) = nearest_module(&node.rel_path, &mut found) {
+                        stats.partial = true;
+                    }
+                }
+            }
+        }
+    }
+
+    for node in &mut tree.nodes {
+        let Some(module) = node.module.as_mut() else {
+            continue;
+        };
+        let stats = found.remove(&node.rel_path).unwrap_or_default();
+        module.tests = Some(TestInfo {
+            files: stats.files,
+            locations: stats.locations.into_iter().collect(),
+            partial: stats.partial,
+        });
+    }
+}
+
+/// Counts the file at `rel` towards its nearest module if it is a test file.
+fn record_file(root: &Path, rel: &Path, found: &mut HashMap<PathBuf, Found>) {
+    let Some(module) = rel
+        .ancestors()
+        .skip(1)
+        .find(|ancestor| found.contains_key(*ancestor))
+    else {
+        return;
+    };
+    let Ok(within) = rel.strip_prefix(module) else {
+        return;
+    };
+    if let Some(location) = test_location(&root.join(rel), within) {
+        let stats = found.entry(module.to_path_buf()).or_default();
+        stats.files += 1;
+        stats.locations.insert(location);
+    }
+}
+
+/// The module that `rel` itself is, or lies in.
+fn nearest_module<'a>(rel: &Path, found: &'a mut HashMap<PathBuf, Found>) -> Option<&'a mut Found> {
+    let module = rel
+        .ancestors()
+        .find(|ancestor| found.contains_key(*ancestor))?
+        .to_path_buf();
+    found.get_mut(&module)
+}
+
+/// Where a test file at `within` (relative to its module) lives, or `None`
+/// when it is not a test file.
+fn test_location(path: &Path, within: &Path) -> Option<String> {
+    let dirs: Vec<&str> = within
+        .parent()
+        .map(|parent| {
+            parent
+                .components()
+                .filter_map(|c| c.as_os_str().to_str())
+                .collect()
+        })
+        .unwrap_or_default();
+    if let Some(index) = dirs.iter().position(|dir| TEST_DIRS.contains(dir)) {
+        return Some(format!("{}/", dirs[..=index].join("/")));
+    }
+
+    let name = within.file_name()?.to_str()?;
+    if !is_test_file(name) && !has_inline_rust_tests(path, name) {
+        return None;
+    }
+    if dirs.is_empty() {
+        Some(".".to_string())
+    } else {
+        Some(dirs.join("/"))
+    }
+}
+
+fn is_test_file(name: &str) -> bool {
+    if name.ends_with("_test.go") {
+        return true;
+    }
+    if let Some(stem) = name.strip_suffix(".py") {
+        return stem.starts_with("test_") || stem.ends_with("_test");
+    }
+    let mut parts = name.rsplit('.');
+    let (Some(extension), Some(infix)) = (parts.next(), parts.next()) else {
+        return false;
+    };
+    JS_EXTENSIONS.contains(&extension) && matches!(infix, "spec" | "test")
+}
+
+/// Rust sources with a `#[cfg(test)]` module in their first
+/// [`MAX_SCANNED_BYTES`] count as test files.
+fn has_inline_rust_tests(path: &Path, name: &str) -> bool {
+    if !name.ends_with(".rs") {
+        return false;
+    }
+    let Ok(file) = fs::File::open(path) else {
+        return false;
+    };
+    let mut content = Vec::new();
+    if file
+        .take(MAX_SCANNED_BYTES)
+        .read_to_end(&mut content)
+        .is_err()
+    {
+        return false;
+    }
+    content
+        .windows(b"#[cfg(test)]".len())
+        .any(|window| window == b"#[cfg(test)]")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_test_file_names() {
+        assert!(is_test_file("handler_test.go"));
+        assert!(is_test_file("app.spec.ts"));
+        assert!(is_test_file("button.test.jsx"));
+        assert!(is_test_file("test_core.py"));
+        assert!(!is_test_file("handler.go"));
+        assert!(!is_test_file("latest.ts"));
+        assert!(!is_test_file("contest.py"));
+    }
+
+    #[test]
+    fn test_dirs_are_their_own_location() {
+        let location = test_location(Path::new("unused"), Path::new("src/__tests__/a.ts"));
+        assert_eq!(location.as_deref(), Some("src/__tests__/"));
+        let location = test_location(Path::new("unused"), Path::new("tests/unit/x.py"));
+        assert_eq!(location.as_deref(), Some("tests/"));
+    }
+}
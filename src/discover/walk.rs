@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::fs;
+use std::ops::ControlFlow;
 use std::path::{Path, PathBuf};
 
 use anyhow::Result;
@@ -7,7 +8,7 @@ use ignore::overrides::{Override, OverrideBuilder};
 use ignore::WalkBuilder;
 
 use crate::config::Config;
-use crate::model::{Node, NodeKind, Tree};
+use crate::model::{Lens, Node, NodeKind, SizeInfo, SortOrder, Tree};
 use anyhow::Context;
 
 fn build_overrides(config: &Config) -> Result<Override> {
@@ -29,7 +30,7 @@ fn build_overrides(config: &Config) -> Result<Override> {
     Ok(builder.build()?)
 }
 
-fn display_root_name(path: &Path) -> String {
+pub fn display_root_name(path: &Path) -> String {
     path.file_name()
         .map(|name| name.to_string_lossy().into_owned())
         .filter(|name| !name.is_empty())
@@ -38,14 +39,7 @@ fn display_root_name(path: &Path) -> String {
 
 fn single_file_tree(path: &Path) -> Tree {
     let name = display_root_name(path);
-    let node = Node {
-        name,
-        rel_path: PathBuf::new(),
-        kind: NodeKind::File,
-        children: Vec::new(),
-        module: None,
-        error: None,
-    };
+    let node = Node::new(name, PathBuf::new(), NodeKind::File, None);
     Tree {
         root_path: path.to_path_buf(),
         root: 0,
@@ -55,6 +49,27 @@ fn single_file_tree(path: &Path) -> Tree {
     }
 }
 
+/// A single entry produced by the walker, in walk order (parents before
+/// their contents).
+#[derive(Clone, Debug)]
+pub struct WalkEntry {
+    pub name: String,
+    pub rel_path: PathBuf,
+    pub kind: NodeKind,
+    pub depth: usize,
+    pub error: Option<String>,
+    /// Recorded for the size lens and `--sort size` only: a file's own size,
+    /// or for a directory at the depth limit, the total of everything below
+    /// it.
+    pub size: Option<SizeInfo>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct WalkSummary {
+    pub count: usize,
+    pub truncated: bool,
+}
+
 pub fn build_tree(config: &Config) -> Result<Tree> {
     let root_path = config.root.clone();
 
@@ -64,41 +79,149 @@ pub fn build_tree(config: &Config) -> Result<Tree> {
         }
     }
 
+    let mut nodes: Vec<Node> = Vec::new();
+    let mut index: HashMap<PathBuf, usize> = HashMap::new();
+
+    let root_node = Node::new(
+        display_root_name(&root_path),
+        PathBuf::new(),
+        NodeKind::Dir,
+        None,
+    );
+    nodes.push(root_node);
+    index.insert(PathBuf::new(), 0);
+
+    let summary = walk(config, |entry| {
+        let node_id = nodes.len();
+        let parent_rel = entry
+            .rel_path
+            .parent()
+            .map(|p| p.to_path_buf())
+            .unwrap_or_else(PathBuf::new);
+        index.insert(entry.rel_path.clone(), node_id);
+        let mut node = Node::new(entry.name, entry.rel_path, entry.kind, entry.error);
+        node.size = entry.size;
+        nodes.push(node);
+
+        let parent_id = index.get(&parent_rel).copied().unwrap_or(0);
+        nodes[parent_id].children.push(node_id);
+        ControlFlow::Continue(())
+    })?;
+
+    if records_sizes(config) {
+        roll_up_sizes(&mut nodes);
+    }
+
+    Ok(Tree {
+        root_path,
+        root: 0,
+        nodes,
+        truncated: summary.truncated,
+        truncated_at: summary.count,
+    })
+}
+
+/// Sizes are needed by the size lens and by `--sort size` under any lens.
+fn records_sizes(config: &Config) -> bool {
+    config.lens == Lens::Size || config.sort == SortOrder::Size
+}
+
+/// Totals every directory's size from its children. Children always come
+/// after their parent in walk order, so a reverse pass sees them first.
+fn roll_up_sizes(nodes: &mut [Node]) {
+    for node_id in (0..nodes.len()).rev() {
+        if !nodes[node_id].is_dir() {
+            continue;
+        }
+        let mut total = nodes[node_id].size.unwrap_or_default();
+        for child_id in nodes[node_id].children.clone() {
+            if let Some(size) = nodes[child_id].size {
+                total.add(size);
+            }
+        }
+        nodes[node_id].size = Some(total);
+    }
+}
+
+pub(crate) fn walk_builder(config: &Config, path: &Path) -> Result<WalkBuilder> {
     let overrides = build_overrides(config)?;
-    let mut builder = WalkBuilder::new(&root_path);
+    let mut builder = WalkBuilder::new(path);
     builder
         .follow_links(false)
         .hidden(!config.hidden)
-        .max_depth(Some(config.depth))
         .overrides(overrides)
         .ignore(config.respect_gitignore)
         .git_ignore(config.respect_gitignore)
         .git_exclude(config.respect_gitignore)
         .git_global(config.respect_gitignore)
         .parents(config.respect_gitignore);
+    Ok(builder)
+}
 
-    let mut nodes: Vec<Node> = Vec::new();
-    let mut index: HashMap<PathBuf, usize> = HashMap::new();
+/// Whether `node` is a directory the walk reached but did not enter because
+/// of the depth limit.
+pub fn is_at_depth_limit(config: &Config, node: &Node) -> bool {
+    node.is_dir() && node.rel_path.components().count() >= config.depth
+}
 
-    let root_node = Node {
-        name: display_root_name(&root_path),
-        rel_path: PathBuf::new(),
-        kind: NodeKind::Dir,
-        children: Vec::new(),
-        module: None,
-        error: None,
+/// Hands every file below `dir` that the walk would have visited without the
+/// depth limit to `visit`. Every entry looked at uses up one item of
+/// `budget`, which callers share across directories so the extra work stays
+/// bounded like the walk itself. Returns `false` when the budget ran out
+/// before the whole subtree was seen.
+pub fn walk_below<F>(config: &Config, dir: &Path, budget: &mut usize, mut visit: F) -> bool
+where
+    F: FnMut(&ignore::DirEntry),
+{
+    let Ok(builder) = walk_builder(config, dir) else {
+        return true;
     };
-    nodes.push(root_node);
-    index.insert(PathBuf::new(), 0);
+    for entry in builder.build().skip(1).filter_map(|entry| entry.ok()) {
+        if *budget == 0 {
+            return false;
+        }
+        *budget -= 1;
+        if entry.file_type().is_some_and(|ft| !ft.is_dir()) {
+            visit(&entry);
+        }
+    }
+    true
+}
+
+/// Size of everything below `dir` that the walk would have visited without
+/// the depth limit, marked partial when `budget` runs out.
+fn subtree_size(config: &Config, dir: &Path, budget: &mut usize) -> SizeInfo {
+    let mut total = SizeInfo::default();
+    let complete = walk_below(config, dir, budget, |entry| {
+        total.add(SizeInfo {
+            bytes: entry.metadata().map(|m| m.len()).unwrap_or(0),
+            files: 1,
+            partial: false,
+        });
+    });
+    total.partial = !complete;
+    total
+}
+
+/// Walks the directory at `config.root`, honouring depth, ignore and
+/// `max_items` settings, and hands every entry except the root to `visit`.
+/// Returning `ControlFlow::Break` from `visit` stops the walk early.
+pub fn walk<F>(config: &Config, mut visit: F) -> Result<WalkSummary>
+where
+    F: FnMut(WalkEntry) -> ControlFlow<()>,
+{
+    let root_path = &config.root;
+    let mut builder = walk_builder(config, root_path)?;
+    builder.max_depth(Some(config.depth));
+    let record_sizes = records_sizes(config);
+    // Sizing below the depth limit is bounded like the walk itself.
+    let mut size_budget = config.max_items;
 
     let mut count = 0usize;
     let mut truncated = false;
 
     for entry_result in builder.build() {
-        if truncated {
-            break;
-        }
-        match entry_result {
+        let entry = match entry_result {
             Ok(entry) => {
                 let path = entry.path();
                 if path == root_path {
@@ -109,7 +232,7 @@ pub fn build_tree(config: &Config) -> Result<Tree> {
                     break;
                 }
 
-                let rel_path = match path.strip_prefix(&root_path) {
+                let rel_path = match path.strip_prefix(root_path) {
                     Ok(rel) => rel.to_path_buf(),
                     Err(_) => path.to_path_buf(),
                 };
@@ -120,25 +243,27 @@ pub fn build_tree(config: &Config) -> Result<Tree> {
                     None => NodeKind::File,
                 };
 
-                let node_id = nodes.len();
-                nodes.push(Node {
+                let size = match kind {
+                    _ if !record_sizes => None,
+                    NodeKind::Dir if entry.depth() >= config.depth => {
+                        Some(subtree_size(config, path, &mut size_budget))
+                    }
+                    NodeKind::Dir => None,
+                    _ => Some(SizeInfo {
+                        bytes: entry.metadata().map(|m| m.len()).unwrap_or(0),
+                        files: 1,
+                        partial: false,
+                    }),
+                };
+
+                WalkEntry {
                     name,
-                    rel_path: rel_path.clone(),
+                    rel_path,
                     kind,
-                    children: Vec::new(),
-                    module: None,
+                    depth: entry.depth(),
                     error: None,
-                });
-                index.insert(rel_path.clone(), node_id);
-
-                let parent_rel = rel_path
-                    .parent()
-                    .map(|p| p.to_path_buf())
-                    .unwrap_or_else(PathBuf::new);
-                let parent_id = index.get(&parent_rel).copied().unwrap_or(0);
-                nodes[parent_id].children.push(node_id);
-
-                count += 1;
+                    size,
+                }
             }
             Err(err) => {
                 let Some(path) = error_path(&err) else {
@@ -152,7 +277,7 @@ pub fn build_tree(config: &Config) -> Result<Tree> {
                     break;
                 }
 
-                let rel_path = match path.strip_prefix(&root_path) {
+                let rel_path = match path.strip_prefix(root_path) {
                     Ok(rel) => rel.to_path_buf(),
                     Err(_) => path.to_path_buf(),
                 };
@@ -168,36 +293,24 @@ pub fn build_tree(config: &Config) -> Result<Tree> {
                     })
                     .unwrap_or_else(|| err.to_string());
 
-                let node_id = nodes.len();
-                nodes.push(Node {
+                WalkEntry {
                     name,
-                    rel_path: rel_path.clone(),
+                    depth: rel_path.components().count(),
+                    rel_path,
                     kind: NodeKind::Error,
-                    children: Vec::new(),
-                    module: None,
                     error: Some(message),
-                });
-                index.insert(rel_path.clone(), node_id);
-
-                let parent_rel = rel_path
-                    .parent()
-                    .map(|p| p.to_path_buf())
-                    .unwrap_or_else(PathBuf::new);
-                let parent_id = index.get(&parent_rel).copied().unwrap_or(0);
-                nodes[parent_id].children.push(node_id);
-
-                count += 1;
+                    size: None,
+                }
             }
+        };
+
+        count += 1;
+        if visit(entry).is_break() {
+            break;
         }
     }
 
-    Ok(Tree {
-        root_path,
-        root: 0,
-        nodes,
-        truncated,
-        truncated_at: count,
-    })
+    Ok(WalkSummary { count, truncated })
 }
 
 fn error_path(err: &ignore::Error) -> Option<&Path> {
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -6,7 +6,8 @@ use anyhow::Result;
 use globset::{Glob, GlobSet, GlobSetBuilder};
 use serde::Deserialize;
 
-use crate::discover::markers::ModuleCandidate;
+use crate::discover::markers::{ModuleCandidate, DOTNET_PROJECT_EXTENSIONS};
+use crate::discover::{bazel, gradle, infra, ruby};
 use crate::error::SmarttreeError;
 use crate::model::Tree;
 use crate::model::{WorkspaceInfo, WorkspaceKind};
@@ -17,6 +18,18 @@ struct PnpmWorkspace {
 }
 
 pub fn detect_workspace(root: &Path) -> Result<Option<WorkspaceInfo>> {
+    // Bazel comes first: when a repo builds with Bazel, its BUILD packages
+    // are the boundaries even if pnpm or Go workspaces also exist inside.
+    if bazel::WORKSPACE_FILES
+        .iter()
+        .any(|name| root.join(name).is_file())
+    {
+        return Ok(Some(WorkspaceInfo {
+            kind: WorkspaceKind::Bazel,
+            patterns: Vec::new(),
+        }));
+    }
+
     let pnpm = root.join("pnpm-workspace.yaml");
     if pnpm.is_file() {
         let content = fs::read_to_string(&pnpm).unwrap_or_default();
@@ -67,6 +80,37 @@ pub fn detect_workspace(root: &Path) -> Result<Option<WorkspaceInfo>> {
         }));
     }
 
+    let pom = root.join("pom.xml");
+    if pom.is_file() {
+        if let Some(patterns) = parse_maven_modules(root) {
+            return Ok(Some(WorkspaceInfo {
+                kind: WorkspaceKind::Maven,
+                patterns,
+            }));
+        }
+    }
+
+    if let Some(patterns) = parse_gradle_settings(root) {
+        return Ok(Some(WorkspaceInfo {
+            kind: WorkspaceKind::Gradle,
+            patterns,
+        }));
+    }
+
+    if let Some(patterns) = parse_dotnet_solution(root) {
+        return Ok(Some(WorkspaceInfo {
+            kind: WorkspaceKind::DotNet,
+            patterns,
+        }));
+    }
+
+    if let Some(patterns) = parse_rails_engines(root) {
+        return Ok(Some(WorkspaceInfo {
+            kind: WorkspaceKind::Rails,
+            patterns,
+        }));
+    }
+
     if root.join("turbo.json").is_file() {
         return Ok(Some(WorkspaceInfo {
             kind: WorkspaceKind::Turbo,
@@ -168,6 +212,202 @@ fn parse_go_work(path: &Path) -> Vec<String> {
     patterns
 }
 
+/// Module directories of a Maven aggregator at `root`, following nested
+/// aggregator poms. Entries are resolved against the pom that declares them,
+/// so `../shared` in `services/pom.xml` is `shared`; those that leave `root`
+/// are skipped since they cannot be package roots below it. `None` when the
+/// root pom declares no modules.
+fn parse_maven_modules(root: &Path) -> Option<Vec<String>> {
+    let modules = pom_modules(&root.join("pom.xml"));
+    if modules.is_empty() {
+        return None;
+    }
+    let mut patterns = Vec::new();
+    let mut pending: Vec<PathBuf> = modules
+        .iter()
+        .filter_map(|module| infra::resolve(Path::new(""), module))
+        .collect();
+    let mut seen = HashSet::new();
+    while let Some(module) = pending.pop() {
+        if module.as_os_str().is_empty() || !seen.insert(module.clone()) {
+            continue;
+        }
+        for nested in pom_modules(&root.join(&module).join("pom.xml")) {
+            pending.extend(infra::resolve(&module, &nested));
+        }
+        patterns.push(path_to_slash(&module));
+    }
+    patterns.sort();
+    Some(patterns)
+}
+
+/// The `<module>` entries of a pom, including those in profiles, as
+/// directories relative to the pom.
+fn pom_modules(path: &Path) -> Vec<String> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let Ok(doc) = roxmltree::Document::parse(&content) else {
+        return Vec::new();
+    };
+    let mut modules: Vec<String> = doc
+        .descendants()
+        .filter(|node| node.has_tag_name("module"))
+        .filter(|node| {
+            node.parent()
+                .is_some_and(|parent| parent.has_tag_name("modules"))
+        })
+        .filter_map(|node| node.text())
+        .map(|text| {
+            let text = text.trim().trim_start_matches("./");
+            // A module may name its pom file rather than its directory.
+            match text.strip_suffix(".xml") {
+                Some(_) => text.rsplit_once('/').map_or("", |(dir, _)| dir),
+                None => text,
+            }
+            .trim_end_matches('/')
+            .to_string()
+        })
+        .filter(|module| !module.is_empty())
+        .collect();
+    modules.dedup();
+    modules
+}
+
+/// Project directories from `settings.gradle(.kts)`: `include`d projects,
+/// with `projectDir` overrides applied, and `includeBuild` composite builds.
+/// `None` when there is no settings file or it includes nothing.
+fn parse_gradle_settings(root: &Path) -> Option<Vec<String>> {
+    let content = gradle::SETTINGS_FILES
+        .iter()
+        .find_map(|name| fs::read_to_string(root.join(name)).ok())?;
+    let mut projects = Vec::new();
+    let mut overrides = HashMap::new();
+    let mut builds = Vec::new();
+    for statement in gradle::statements(&content) {
+        if let Some(rest) = statement.strip_prefix("includeBuild") {
+            builds.extend(gradle::quoted_strings(rest).into_iter().take(1));
+        } else if let Some(rest) = statement.strip_prefix("include") {
+            if rest.starts_with([' ', '(']) {
+                projects.extend(gradle::quoted_strings(rest));
+            }
+        } else if statement.starts_with("project(") && statement.contains(".projectDir") {
+            // `project(":app").projectDir = file("mobile/app")`, also with
+            // `File(rootDir, "...")` or `new File(settingsDir, '...')`.
+            let strings = gradle::quoted_strings(&statement);
+            if let [project, .., dir] = strings.as_slice() {
+                overrides.insert(project_dir(project), dir.clone());
+            }
+        }
+    }
+    if projects.is_empty() && builds.is_empty() {
+        return None;
+    }
+
+    let mut patterns: Vec<String> = projects
+        .iter()
+        .map(|project| {
+            let default = project_dir(project);
+            overrides.remove(&default).unwrap_or(default)
+        })
+        .chain(builds)
+        .map(|dir| normalize_pattern(&dir).trim_end_matches('/').to_string())
+        .filter(|dir| !dir.is_empty() && !dir.split('/').any(|part| part == ".."))
+        .collect();
+    patterns.sort();
+    patterns.dedup();
+    Some(patterns)
+}
+
+/// The default directory of a Gradle project path: `:lib:core` is `lib/core`.
+fn project_dir(project: &str) -> String {
+    project.trim_start_matches(':').replace(':', "/")
+}
+
+/// Project directories listed in the first `.sln` or `.slnx` file at the
+/// root, by name. `None` when there is no solution or it lists no projects.
+fn parse_dotnet_solution(root: &Path) -> Option<Vec<String>> {
+    let mut solutions: Vec<PathBuf> = fs::read_dir(root)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|extension| extension == "sln" || extension == "slnx")
+        })
+        .collect();
+    solutions.sort();
+    let solution = solutions.first()?;
+    let content = fs::read_to_string(solution).ok()?;
+    let project_files = if solution.extension().is_some_and(|e| e == "slnx") {
+        slnx_projects(&content)
+    } else {
+        sln_projects(&content)
+    };
+
+    let mut patterns: Vec<String> = project_files
+        .iter()
+        .filter(|file| {
+            DOTNET_PROJECT_EXTENSIONS
+                .iter()
+                .any(|extension| file.ends_with(extension))
+        })
+        .map(|file| {
+            let file = file.replace('\\', "/");
+            let dir = file.rsplit_once('/').map_or("", |(dir, _)| dir);
+            normalize_pattern(dir)
+        })
+        .filter(|dir| !dir.is_empty() && !dir.split('/').any(|part| part == ".."))
+        .collect();
+    if patterns.is_empty() {
+        return None;
+    }
+    patterns.sort();
+    patterns.dedup();
+    Some(patterns)
+}
+
+/// Project paths from `Project("{type}") = "Name", "path", "{guid}"` lines.
+/// Solution folders appear the same way but their path is just a name, and
+/// are dropped by the caller's extension check.
+fn sln_projects(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let (_, rest) = line.trim().strip_prefix("Project(")?.split_once('=')?;
+            rest.split('"').nth(3).map(str::to_string)
+        })
+        .collect()
+}
+
+/// `Path` attributes of `<Project>` elements, inside folders or not.
+fn slnx_projects(content: &str) -> Vec<String> {
+    let Ok(doc) = roxmltree::Document::parse(content) else {
+        return Vec::new();
+    };
+    doc.descendants()
+        .filter(|node| node.has_tag_name("Project"))
+        .filter_map(|node| node.attribute("Path").map(str::to_string))
+        .collect()
+}
+
+/// One pattern per engine directory of a Rails app at `root`, e.g.
+/// `components/*` for a componentised app. A `Gemfile` alone is not enough:
+/// JavaScript and mobile apps often carry one for fastlane or CocoaPods.
+fn parse_rails_engines(root: &Path) -> Option<Vec<String>> {
+    if !root.join("Gemfile").is_file() || !ruby::is_rails_app(root) {
+        return None;
+    }
+    let patterns: Vec<String> = ruby::ENGINE_DIRS
+        .iter()
+        .filter(|dir| root.join(dir).is_dir())
+        .map(|dir| format!("{dir}/*"))
+        .collect();
+    (!patterns.is_empty()).then_some(patterns)
+}
+
 fn normalize_pattern(pattern: &str) -> String {
     let trimmed = pattern.trim();
     trimmed
@@ -217,32 +457,55 @@ fn roots_from_patterns(tree: &Tree, patterns: &[String]) -> Result<Vec<PathBuf>>
     Ok(roots.into_iter().collect())
 }
 
+/// Conventional directories that hold a workspace's packages.
+const GROUP_DIRS: [&str; 4] = ["packages", "apps", "services", "libs"];
+
+/// Returns the conventional group directory (`packages`, `apps`, ...) that a
+/// package path lives under, if any. The group dir itself is not a package.
+pub fn group_dir(rel: &Path) -> Option<&str> {
+    let mut comps = rel.components();
+    let first = comps.next()?.as_os_str().to_str()?;
+    if !GROUP_DIRS.contains(&first) {
+        return None;
+    }
+    comps.next()?;
+    Some(first)
+}
+
 fn heuristic_package_roots(tree: &Tree, candidates: &[ModuleCandidate]) -> Vec<PathBuf> {
-    const GROUP_DIRS: [&str; 4] = ["packages", "apps", "services", "libs"];
     let mut roots = HashSet::new();
     for candidate in candidates {
         let rel = &tree.nodes[candidate.node_id].rel_path;
-        let mut comps = rel.components();
-        let Some(first) = comps.next() else { continue };
-        let Some(first_str) = first.as_os_str().to_str() else {
-            continue;
-        };
-        if !GROUP_DIRS.contains(&first_str) {
-            continue;
+        if group_dir(rel).is_some() {
+            roots.insert(rel.clone());
         }
-        if comps.next().is_none() {
-            continue;
-        }
-        roots.insert(rel.clone());
     }
     roots.into_iter().collect()
 }
 
+/// Every directory with a BUILD file is a Bazel package.
+fn bazel_package_roots(tree: &Tree, candidates: &[ModuleCandidate]) -> Vec<PathBuf> {
+    candidates
+        .iter()
+        .filter(|candidate| {
+            candidate
+                .markers
+                .iter()
+                .any(|marker| bazel::BUILD_FILES.contains(&marker.as_str()))
+        })
+        .map(|candidate| tree.nodes[candidate.node_id].rel_path.clone())
+        .filter(|rel| !rel.as_os_str().is_empty())
+        .collect()
+}
+
 pub fn resolve_package_roots(
     tree: &Tree,
     info: &WorkspaceInfo,
     candidates: &[ModuleCandidate],
 ) -> Result<Vec<PathBuf>> {
+    if info.kind == WorkspaceKind::Bazel {
+        return Ok(bazel_package_roots(tree, candidates));
+    }
     let mut roots = if info.patterns.is_empty() {
         Vec::new()
     } else {
@@ -256,6 +519,120 @@ pub fn resolve_package_roots(
     Ok(roots)
 }
 
+/// How [`PackageRootMatcher`] decides whether a directory lies in a package.
+enum RootRule {
+    /// The directory or one of its ancestors matches the workspace patterns.
+    /// Used only when the patterns match something, as in
+    /// [`resolve_package_roots`].
+    Patterns(GlobSet),
+    /// The directory holds a BUILD file, as in [`bazel_package_roots`].
+    BuildFiles,
+    /// The directory is inside a group dir such as `packages/`, as in
+    /// [`heuristic_package_roots`].
+    GroupDirs,
+    /// Nothing to go on, so every directory is accepted.
+    Any,
+}
+
+/// Decides which module directories belong to a workspace. The batch
+/// discovery and the NDJSON stream both use it, so they agree on which
+/// directories are modules. The stream sees directories one at a time, so the
+/// rule only looks at a directory's path and its own marker files.
+pub struct PackageRootMatcher {
+    rule: RootRule,
+}
+
+impl PackageRootMatcher {
+    pub fn new(info: &WorkspaceInfo, root: &Path) -> Result<Self> {
+        let rule = if info.kind == WorkspaceKind::Bazel {
+            RootRule::BuildFiles
+        } else if info
+            .patterns
+            .iter()
+            .any(|pattern| pattern_matches_below(root, &normalize_pattern(pattern)))
+        {
+            RootRule::Patterns(build_globset(&info.patterns)?)
+        } else if has_group_packages(root) {
+            RootRule::GroupDirs
+        } else {
+            RootRule::Any
+        };
+        Ok(PackageRootMatcher { rule })
+    }
+
+    /// Whether a module at `rel` with the given marker files is a package
+    /// root or lies inside one.
+    pub fn is_within_package(&self, rel: &Path, markers: &[String]) -> bool {
+        match &self.rule {
+            RootRule::Patterns(globset) => {
+                rel.ancestors()
+                    .filter(|ancestor| !ancestor.as_os_str().is_empty())
+                    .any(|ancestor| globset.is_match(path_to_slash(ancestor)))
+                    || markers
+                        .iter()
+                        .any(|name| globset.is_match(path_to_slash(&rel.join(name))))
+            }
+            RootRule::BuildFiles => markers
+                .iter()
+                .any(|name| bazel::BUILD_FILES.contains(&name.as_str())),
+            RootRule::GroupDirs => group_dir(rel).is_some(),
+            RootRule::Any => true,
+        }
+    }
+}
+
+/// Whether a workspace pattern such as `packages/*` names anything on disk
+/// below `dir`, checking one path component at a time.
+fn pattern_matches_below(dir: &Path, pattern: &str) -> bool {
+    let (component, rest) = match pattern.split_once('/') {
+        Some((component, rest)) => (component, Some(rest)),
+        None => (pattern, None),
+    };
+    let matches_rest = |path: &Path| match rest {
+        Some(rest) => path.is_dir() && pattern_matches_below(path, rest),
+        None => true,
+    };
+    if component == "**" {
+        let rest = rest.unwrap_or("*");
+        return pattern_matches_below(dir, rest)
+            || subdirs(dir).any(|sub| pattern_matches_below(&sub, pattern));
+    }
+    if !component.contains(['*', '?', '[', '{']) {
+        let path = dir.join(component);
+        return path.exists() && matches_rest(&path);
+    }
+    let Ok(glob) = Glob::new(component) else {
+        return false;
+    };
+    let matcher = glob.compile_matcher();
+    fs::read_dir(dir).is_ok_and(|entries| {
+        entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| matcher.is_match(entry.file_name()))
+            .any(|entry| matches_rest(&entry.path()))
+    })
+}
+
+fn subdirs(dir: &Path) -> impl Iterator<Item = PathBuf> {
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+}
+
+/// Whether any group dir under `root` has a directory inside it.
+fn has_group_packages(root: &Path) -> bool {
+    GROUP_DIRS.iter().any(|dir| {
+        fs::read_dir(root.join(dir)).is_ok_and(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .any(|entry| entry.path().is_dir())
+        })
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -279,6 +656,101 @@ mod tests {
         assert_eq!(patterns, vec!["libs/*"]);
     }
 
+    #[test]
+    fn parse_maven_modules_follows_aggregators() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let pom = |modules: &str| format!("<project><modules>{modules}</modules></project>");
+        fs::write(
+            dir.path().join("pom.xml"),
+            pom("<module>core</module><module>services</module>"),
+        )
+        .expect("write");
+        fs::create_dir_all(dir.path().join("services/api")).expect("mkdir");
+        fs::write(
+            dir.path().join("services/pom.xml"),
+            pom("<module>api</module><module>../shared</module><module>../../outside</module>"),
+        )
+        .expect("write");
+        let patterns = parse_maven_modules(dir.path()).expect("patterns");
+        assert_eq!(patterns, vec!["core", "services", "services/api", "shared"]);
+    }
+
+    #[test]
+    fn pattern_matches_below_checks_the_disk() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        fs::create_dir_all(dir.path().join("packages/ui/src")).expect("mkdir");
+        assert!(pattern_matches_below(dir.path(), "packages/*"));
+        assert!(pattern_matches_below(dir.path(), "**/src"));
+        assert!(pattern_matches_below(dir.path(), "packages/ui"));
+        assert!(!pattern_matches_below(dir.path(), "modules/*"));
+        assert!(!pattern_matches_below(dir.path(), "packages/*/lib"));
+    }
+
+    #[test]
+    fn parse_gradle_settings_includes() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        fs::write(
+            dir.path().join("settings.gradle.kts"),
+            r#"rootProject.name = "shop"
+include(":app", ":lib:core")
+include(":legacy")
+project(":legacy").projectDir = file("old/legacy-module")
+includeBuild("build-logic")
+"#,
+        )
+        .expect("write");
+        let patterns = parse_gradle_settings(dir.path()).expect("patterns");
+        assert_eq!(
+            patterns,
+            vec!["app", "build-logic", "lib/core", "old/legacy-module"]
+        );
+    }
+
+    #[test]
+    fn parse_rails_engines_needs_rails_app() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        fs::create_dir_all(dir.path().join("components")).expect("mkdir");
+        fs::create_dir_all(dir.path().join("config")).expect("mkdir");
+        assert_eq!(parse_rails_engines(dir.path()), None);
+        fs::write(
+            dir.path().join("Gemfile"),
+            "source \"https://rubygems.org\"\n",
+        )
+        .expect("write");
+        assert_eq!(parse_rails_engines(dir.path()), None);
+        fs::write(dir.path().join("config/application.rb"), "").expect("write");
+        assert_eq!(
+            parse_rails_engines(dir.path()),
+            Some(vec!["components/*".to_string()])
+        );
+    }
+
+    #[test]
+    fn parse_dotnet_solution_projects() {
+        let sln = r#"
+Microsoft Visual Studio Solution File, Format Version 12.00
+Project("{2150E333-8FDC-42A3-9474-1A3956D46DE8}") = "src", "src", "{11111111-1111-1111-1111-111111111111}"
+EndProject
+Project("{FAE04EC0-301F-11D3-BF4B-00C04F79EFBC}") = "Shop.Api", "src\Shop.Api\Shop.Api.csproj", "{22222222-2222-2222-2222-222222222222}"
+EndProject
+"#;
+        assert_eq!(sln_projects(sln)[1], r"src\Shop.Api\Shop.Api.csproj");
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        fs::write(
+            dir.path().join("Shop.slnx"),
+            r#"<Solution>
+  <Folder Name="/src/">
+    <Project Path="src/Shop.Api/Shop.Api.csproj" />
+  </Folder>
+  <Project Path="tests/Shop.Tests/Shop.Tests.fsproj" />
+</Solution>"#,
+        )
+        .expect("write");
+        let patterns = parse_dotnet_solution(dir.path()).expect("patterns");
+        assert_eq!(patterns, vec!["src/Shop.Api", "tests/Shop.Tests"]);
+    }
+
     #[test]
     fn parse_go_work_use_block() {
         let dir = tempfile::tempdir().expect("tempdir");
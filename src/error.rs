@@ -28,4 +28,11 @@ pub enum SmarttreeError {
         #[source]
         source: globset::Error,
     },
+    #[error("failed to run git: {source}")]
+    GitSpawn {
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("`git {command}` failed: {message}")]
+    GitFailed { command: String, message: String },
 }
@@ -4,6 +4,7 @@ use smarttree::cli::Cli;
 use smarttree::config;
 use smarttree::discover;
 use smarttree::error::SmarttreeError;
+use smarttree::model::Format;
 use smarttree::render;
 
 fn main() {
@@ -32,6 +33,10 @@ fn run() -> anyhow::Result<()> {
     }
 
     let config = config::load(&cli)?;
+    if config.format == Format::Ndjson {
+        return render::ndjson::stream(&config, &mut std::io::stdout().lock());
+    }
+
     let result = discover::discover(&config)?;
     let output = render::render(&result.tree, result.workspace.as_ref(), &config);
     print!("{output}");
@@ -53,6 +58,13 @@ fn hint_for_error(err: &anyhow::Error) -> Option<String> {
             "Unable to write config at {}. Check permissions or choose a different path with --config.",
             path.display()
         )),
+        SmarttreeError::GitSpawn { .. } => {
+            Some("This view needs the `git` binary on your PATH.".to_string())
+        }
+        SmarttreeError::GitFailed { .. } => Some(
+            "Make sure the path is inside a git repository and any revision or date you passed is valid."
+                .to_string(),
+        ),
         _ => None,
     }
 }
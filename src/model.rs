@@ -1,4 +1,6 @@
+use std::fmt;
 use std::path::PathBuf;
+use std::str::FromStr;
 
 use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
@@ -8,6 +10,23 @@ use serde::{Deserialize, Serialize};
 pub enum Lens {
     Module,
     Files,
+    Deps,
+    Owners,
+    Size,
+    Churn,
+    Entrypoints,
+    Languages,
+    Tests,
+    Docs,
+    Infra,
+}
+
+impl Lens {
+    /// Whether the tree is narrowed to modules, their marker files and key
+    /// dirs. The files and size lenses show everything.
+    pub fn filters_to_modules(self) -> bool {
+        !matches!(self, Lens::Files | Lens::Size)
+    }
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, ValueEnum)]
@@ -15,6 +34,133 @@ pub enum Lens {
 pub enum Format {
     Text,
     Md,
+    Json,
+    Mermaid,
+    Dot,
+    Html,
+    Ndjson,
+    Context,
+    Csv,
+    Tsv,
+    Mdtable,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum MdStyle {
+    List,
+    Fence,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Column {
+    Path,
+    Kind,
+    Summary,
+    Markers,
+    Workspace,
+}
+
+impl Column {
+    pub const ALL: [Column; 5] = [
+        Column::Path,
+        Column::Kind,
+        Column::Summary,
+        Column::Markers,
+        Column::Workspace,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Column::Path => "path",
+            Column::Kind => "kind",
+            Column::Summary => "summary",
+            Column::Markers => "markers",
+            Column::Workspace => "workspace",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum When {
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Name,
+    Size,
+}
+
+/// A byte count, written either as a plain number or with a binary unit
+/// suffix (`512`, `10K`, `1.5MiB`, `2G`).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Deserialize)]
+#[serde(try_from = "ByteSizeRepr")]
+pub struct ByteSize(pub u64);
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ByteSizeRepr {
+    Bytes(u64),
+    Text(String),
+}
+
+impl TryFrom<ByteSizeRepr> for ByteSize {
+    type Error = String;
+
+    fn try_from(repr: ByteSizeRepr) -> Result<Self, Self::Error> {
+        match repr {
+            ByteSizeRepr::Bytes(bytes) => Ok(ByteSize(bytes)),
+            ByteSizeRepr::Text(text) => text.parse(),
+        }
+    }
+}
+
+impl FromStr for ByteSize {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+        let split = text
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(text.len());
+        let (number, unit) = text.split_at(split);
+        let number: f64 = number
+            .parse()
+            .map_err(|_| format!("invalid size '{text}'"))?;
+        let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+            "" | "b" => 1,
+            "k" | "kb" | "kib" => 1 << 10,
+            "m" | "mb" | "mib" => 1 << 20,
+            "g" | "gb" | "gib" => 1 << 30,
+            "t" | "tb" | "tib" => 1 << 40,
+            _ => return Err(format!("invalid size unit in '{text}'")),
+        };
+        Ok(ByteSize((number * multiplier as f64) as u64))
+    }
+}
+
+/// Human-readable form with binary units, e.g. `12.3 KiB`.
+impl fmt::Display for ByteSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+        let mut value = self.0 as f64;
+        let mut unit = 0;
+        while value >= 1024.0 && unit < UNITS.len() - 1 {
+            value /= 1024.0;
+            unit += 1;
+        }
+        if unit == 0 {
+            write!(f, "{} B", self.0)
+        } else {
+            write!(f, "{value:.1} {}", UNITS[unit])
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -24,6 +170,16 @@ pub enum NodeKind {
     Error,
 }
 
+impl NodeKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            NodeKind::Dir => "dir",
+            NodeKind::File => "file",
+            NodeKind::Error => "error",
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
 pub enum ModuleKind {
     Node,
@@ -32,6 +188,8 @@ pub enum ModuleKind {
     Go,
     Java,
     DotNet,
+    Bazel,
+    Ruby,
     Unknown,
 }
 
@@ -44,9 +202,25 @@ impl ModuleKind {
             ModuleKind::Go => "[go]",
             ModuleKind::Java => "[java]",
             ModuleKind::DotNet => "[dotnet]",
+            ModuleKind::Bazel => "[bazel]",
+            ModuleKind::Ruby => "[rb]",
             ModuleKind::Unknown => "[module]",
         }
     }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ModuleKind::Node => "node",
+            ModuleKind::Python => "python",
+            ModuleKind::Rust => "rust",
+            ModuleKind::Go => "go",
+            ModuleKind::Java => "java",
+            ModuleKind::DotNet => "dotnet",
+            ModuleKind::Bazel => "bazel",
+            ModuleKind::Ruby => "ruby",
+            ModuleKind::Unknown => "unknown",
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -54,6 +228,174 @@ pub struct ModuleInfo {
     pub kind: ModuleKind,
     pub summary: Option<String>,
     pub markers: Vec<String>,
+    /// Other modules in the tree this one depends on (deps lens only).
+    pub dependencies: Vec<PathBuf>,
+    /// Other modules in the tree that depend on this one (deps lens only).
+    pub dependents: Vec<PathBuf>,
+    /// Why the module is part of a `--changed-since` view, if it is.
+    pub affected: Option<Affected>,
+    /// Runnable surfaces declared by the module (entrypoints lens only).
+    pub entrypoints: Vec<Entrypoint>,
+    /// Test files found in the module (tests lens only).
+    pub tests: Option<TestInfo>,
+    /// Documentation files found in the module (docs lens only).
+    pub docs: Option<DocsInfo>,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TestInfo {
+    pub files: u64,
+    /// Where the test files live, relative to the module: test dirs such as
+    /// `tests/`, or the directories holding colocated test files (`.` for
+    /// the module itself). Sorted.
+    pub locations: Vec<String>,
+    /// Set when searching below the depth limit stopped short because the
+    /// item budget ran out, so there may be more.
+    pub partial: bool,
+}
+
+/// Documentation files directly inside a module. File fields hold the name
+/// as found on disk.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DocsInfo {
+    pub readme: Option<String>,
+    /// The README's first line, without Markdown heading markers.
+    pub title: Option<String>,
+    pub changelog: Option<String>,
+    /// A `docs/` or `doc/` directory, with its name as found.
+    pub docs_dir: Option<String>,
+    pub license: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum InfraKind {
+    Docker,
+    Compose,
+    Kubernetes,
+    Helm,
+    Terraform,
+    Workflow,
+}
+
+impl InfraKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            InfraKind::Docker => "docker",
+            InfraKind::Compose => "compose",
+            InfraKind::Kubernetes => "k8s",
+            InfraKind::Helm => "helm",
+            InfraKind::Terraform => "terraform",
+            InfraKind::Workflow => "workflow",
+        }
+    }
+}
+
+/// A build or deploy file. `path` is relative to the tree root; Helm charts
+/// and Terraform configurations point at their directory.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct InfraFile {
+    pub kind: InfraKind,
+    pub path: PathBuf,
+    /// Base image, services, resource kinds, chart or workflow name.
+    pub detail: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum EntrypointKind {
+    Bin,
+    Main,
+    Export,
+    Script,
+}
+
+impl EntrypointKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            EntrypointKind::Bin => "bin",
+            EntrypointKind::Main => "main",
+            EntrypointKind::Export => "export",
+            EntrypointKind::Script => "script",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Entrypoint {
+    pub kind: EntrypointKind,
+    /// Command, export path or script name.
+    pub name: String,
+    /// File or command it runs, relative to the module, when known.
+    pub target: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Affected {
+    /// Contains a changed file.
+    Changed,
+    /// Depends, directly or not, on a changed module.
+    Dependent,
+}
+
+impl Affected {
+    pub fn label(self) -> &'static str {
+        match self {
+            Affected::Changed => "changed",
+            Affected::Dependent => "dependent",
+        }
+    }
+}
+
+impl ModuleInfo {
+    pub fn new(kind: ModuleKind, summary: Option<String>, markers: Vec<String>) -> Self {
+        ModuleInfo {
+            kind,
+            summary,
+            markers,
+            dependencies: Vec::new(),
+            dependents: Vec::new(),
+            affected: None,
+            entrypoints: Vec::new(),
+            tests: None,
+            docs: None,
+        }
+    }
+}
+
+/// Bytes and file count of an entry; for directories, of everything below
+/// them (size lens or `--sort size` only).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SizeInfo {
+    pub bytes: u64,
+    pub files: u64,
+    /// Set when the total stopped short because the walk's item budget ran
+    /// out, so the real size is larger.
+    pub partial: bool,
+}
+
+impl SizeInfo {
+    pub fn add(&mut self, other: SizeInfo) {
+        self.bytes += other.bytes;
+        self.files += other.files;
+        self.partial |= other.partial;
+    }
+}
+
+/// Git activity for an entry within the `--since` window (churn lens only).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ChurnInfo {
+    pub commits: u64,
+    pub added: u64,
+    pub deleted: u64,
+    /// Set on modules among the most active in the tree.
+    pub hot: bool,
+}
+
+/// Files and lines of one language under an entry (languages lens only).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LanguageCount {
+    pub language: &'static str,
+    pub files: u64,
+    pub lines: u64,
 }
 
 #[derive(Clone, Debug)]
@@ -64,9 +406,38 @@ pub struct Node {
     pub children: Vec<usize>,
     pub module: Option<ModuleInfo>,
     pub error: Option<String>,
+    /// CODEOWNERS owners of this entry (owners lens only).
+    pub owners: Vec<String>,
+    pub size: Option<SizeInfo>,
+    pub churn: Option<ChurnInfo>,
+    /// Per-language counts, largest first (languages lens only).
+    pub languages: Vec<LanguageCount>,
+    /// Set when counting below the depth limit stopped short because the
+    /// item budget ran out, so `languages` misses some files.
+    pub languages_partial: bool,
+    /// Build and deploy files attached to this node, its nearest module
+    /// (infra lens only).
+    pub infra: Vec<InfraFile>,
 }
 
 impl Node {
+    pub fn new(name: String, rel_path: PathBuf, kind: NodeKind, error: Option<String>) -> Self {
+        Node {
+            name,
+            rel_path,
+            kind,
+            children: Vec::new(),
+            module: None,
+            error,
+            owners: Vec::new(),
+            size: None,
+            churn: None,
+            languages: Vec::new(),
+            languages_partial: false,
+            infra: Vec::new(),
+        }
+    }
+
     pub fn is_dir(&self) -> bool {
         matches!(self.kind, NodeKind::Dir)
     }
@@ -90,6 +461,11 @@ pub enum WorkspaceKind {
     Go,
     Turbo,
     Nx,
+    Maven,
+    Gradle,
+    DotNet,
+    Bazel,
+    Rails,
 }
 
 impl WorkspaceKind {
@@ -102,6 +478,11 @@ impl WorkspaceKind {
             WorkspaceKind::Go => "go",
             WorkspaceKind::Turbo => "turbo",
             WorkspaceKind::Nx => "nx",
+            WorkspaceKind::Maven => "maven",
+            WorkspaceKind::Gradle => "gradle",
+            WorkspaceKind::DotNet => "dotnet",
+            WorkspaceKind::Bazel => "bazel",
+            WorkspaceKind::Rails => "rails",
         }
     }
 }
@@ -117,3 +498,18 @@ pub struct WorkspaceResolved {
     pub kind: WorkspaceKind,
     pub package_roots: Vec<PathBuf>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_formats_byte_sizes() {
+        assert_eq!("512".parse::<ByteSize>(), Ok(ByteSize(512)));
+        assert_eq!("10K".parse::<ByteSize>(), Ok(ByteSize(10 * 1024)));
+        assert_eq!("1.5MiB".parse::<ByteSize>(), Ok(ByteSize(1536 * 1024)));
+        assert!("3 parsecs".parse::<ByteSize>().is_err());
+        assert_eq!(ByteSize(999).to_string(), "999 B");
+        assert_eq!(ByteSize(12_595).to_string(), "12.3 KiB");
+    }
+}
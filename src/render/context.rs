@@ -0,0 +1,176 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::Config;
+use crate::discover::summary::readme_candidates;
+use crate::model::{Tree, WorkspaceResolved};
+use crate::render::select::path_to_slash;
+use crate::render::text::render_text;
+
+/// Files smaller than this are not worth including in truncated form.
+const MIN_PARTIAL_TOKENS: usize = 32;
+
+/// Rough token estimate used for budgeting: about four bytes per token.
+fn estimate_tokens(text: &str) -> usize {
+    text.len().div_ceil(4)
+}
+
+/// Renders the module tree followed by the manifests and READMEs of each
+/// module, in priority order (root, workspace packages, other modules), until
+/// `token_budget` is used up.
+pub fn render_context(
+    tree: &Tree,
+    workspace: Option<&WorkspaceResolved>,
+    config: &Config,
+) -> String {
+    let mut budget = Budget {
+        remaining: config.token_budget,
+        out: String::new(),
+    };
+
+    budget.push_always(&format!(
+        "# Project context: {}\n\n## Structure\n\n",
+        tree.nodes[tree.root].name
+    ));
+    let structure = render_text(tree, workspace, config);
+    budget.push_fitting(&structure);
+    budget.push_always("\n\n## Files\n");
+
+    let files = prioritized_files(tree, workspace);
+    let mut omitted = Vec::new();
+    for rel in files {
+        let Ok(content) = fs::read_to_string(tree.root_path.join(&rel)) else {
+            continue;
+        };
+        let path = path_to_slash(&rel);
+        let open = format!("\n===== file: {path} =====\n");
+        let close = format!("\n===== end: {path} =====\n");
+        let overhead = estimate_tokens(&open) + estimate_tokens(&close);
+        let needed = overhead + estimate_tokens(&content);
+
+        if needed <= budget.remaining {
+            budget.push_always(&open);
+            budget.push_always(content.trim_end());
+            budget.push_always(&close);
+        } else if budget.remaining >= overhead + MIN_PARTIAL_TOKENS {
+            budget.push_always(&open);
+            budget.remaining = budget.remaining.saturating_sub(estimate_tokens(&close));
+            budget.push_fitting(content.trim_end());
+            budget.out.push_str(&close);
+        } else {
+            omitted.push(path);
+        }
+    }
+
+    if !omitted.is_empty() {
+        budget.out.push_str(&format!(
+            "\n(omitted to fit token budget of {}: {})\n",
+            config.token_budget,
+            omitted.join(", ")
+        ));
+    }
+
+    budget.out
+}
+
+struct Budget {
+    remaining: usize,
+    out: String,
+}
+
+impl Budget {
+    /// Appends text that must always be present, even past the budget.
+    fn push_always(&mut self, text: &str) {
+        self.remaining = self.remaining.saturating_sub(estimate_tokens(text));
+        self.out.push_str(text);
+    }
+
+    /// Appends as many whole lines of `text` as the remaining budget allows.
+    fn push_fitting(&mut self, text: &str) {
+        if estimate_tokens(text) <= self.remaining {
+            self.push_always(text);
+            return;
+        }
+        let marker = "\n... (truncated to fit token budget)";
+        let limit = self
+            .remaining
+            .saturating_sub(estimate_tokens(marker))
+            .saturating_mul(4);
+        let mut used = 0;
+        for line in text.lines() {
+            if used + line.len() + 1 > limit {
+                break;
+            }
+            if used > 0 {
+                self.out.push('\n');
+            }
+            self.out.push_str(line);
+            used += line.len() + 1;
+        }
+        self.out.push_str(marker);
+        self.remaining = 0;
+    }
+}
+
+/// Manifests and READMEs to include, root module first, then workspace
+/// packages, then any other module, each group ordered by path.
+fn prioritized_files(tree: &Tree, workspace: Option<&WorkspaceResolved>) -> Vec<PathBuf> {
+    let package_roots = workspace.map(|w| w.package_roots.as_slice()).unwrap_or(&[]);
+    let mut modules: Vec<(u8, String, &crate::model::Node)> = tree
+        .nodes
+        .iter()
+        .filter(|node| node.module.is_some())
+        .map(|node| {
+            let rank = if node.rel_path.as_os_str().is_empty() {
+                0
+            } else if package_roots.contains(&node.rel_path) {
+                1
+            } else {
+                2
+            };
+            (rank, path_to_slash(&node.rel_path), node)
+        })
+        .collect();
+    modules.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+
+    let mut files = Vec::new();
+    let mut seen = HashSet::new();
+    for (_, _, node) in modules {
+        let Some(module) = &node.module else {
+            continue;
+        };
+        let module_path = tree.root_path.join(&node.rel_path);
+        let names = module
+            .markers
+            .iter()
+            .cloned()
+            .chain(readme_candidates(&module_path));
+        for name in names {
+            let rel = node.rel_path.join(name);
+            if seen.insert(rel.clone()) {
+                files.push(rel);
+            }
+        }
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncates_whole_lines_within_budget() {
+        let mut budget = Budget {
+            remaining: 15,
+            out: String::new(),
+        };
+        budget
+            .push_fitting("aaaaaaaaaaaaaaaaaaaa\nbbbbbbbbbbbbbbbbbbbb\ncccccccccccccccccccc\nddd");
+        assert!(budget.out.starts_with("aaaaaaaaaaaaaaaaaaaa\n"));
+        assert!(!budget.out.contains("bbb"));
+        assert!(budget.out.ends_with("... (truncated to fit token budget)"));
+        assert_eq!(budget.remaining, 0);
+    }
+}
@@ -0,0 +1,127 @@
+use std::collections::BTreeMap;
+
+use crate::config::Config;
+use crate::discover::workspace::group_dir;
+use crate::model::{ModuleKind, Node, NodeKind, Tree, WorkspaceResolved};
+use crate::render::select::Selector;
+
+/// Renders the lens tree as a Graphviz digraph. Entries below a workspace
+/// group dir (`packages/`, `apps/`, ...) are placed in a cluster per group,
+/// and module nodes are filled with a colour per `ModuleKind`.
+pub fn render_dot(tree: &Tree, workspace: Option<&WorkspaceResolved>, config: &Config) -> String {
+    let selector = Selector::new(tree, config);
+    let mut entries = Vec::new();
+    let mut edges = Vec::new();
+    entries.push((tree.root, None));
+    collect(tree, &selector, tree.root, 0, &mut entries, &mut edges);
+
+    let mut lines = vec![
+        "digraph smarttree {".to_string(),
+        "    rankdir=LR;".to_string(),
+        "    node [shape=box, fontname=\"Helvetica\"];".to_string(),
+    ];
+
+    let mut clusters: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for (id, (node_id, group)) in entries.iter().enumerate() {
+        let node = &tree.nodes[*node_id];
+        let ws = if *node_id == tree.root {
+            workspace
+        } else {
+            None
+        };
+        let line = format!("n{id} [{}];", node_attrs(node, ws));
+        match group {
+            Some(group) => clusters.entry(group).or_default().push(line),
+            None => lines.push(format!("    {line}")),
+        }
+    }
+
+    for (group, nodes) in clusters {
+        lines.push(format!("    subgraph \"cluster_{}\" {{", escape(group)));
+        lines.push(format!("        label=\"{}/\";", escape(group)));
+        lines.push("        style=rounded;".to_string());
+        for line in nodes {
+            lines.push(format!("        {line}"));
+        }
+        lines.push("    }".to_string());
+    }
+
+    for (from, to) in edges {
+        lines.push(format!("    n{from} -> n{to};"));
+    }
+    lines.push("}".to_string());
+    lines.join("\n")
+}
+
+fn collect<'a>(
+    tree: &'a Tree,
+    selector: &Selector<'_>,
+    node_id: usize,
+    graph_id: usize,
+    entries: &mut Vec<(usize, Option<&'a str>)>,
+    edges: &mut Vec<(usize, usize)>,
+) {
+    for child_id in selector.children(node_id) {
+        let child = &tree.nodes[child_id];
+        let child_graph_id = entries.len();
+        entries.push((child_id, group_dir(&child.rel_path)));
+        edges.push((graph_id, child_graph_id));
+        if child.kind == NodeKind::Dir {
+            collect(tree, selector, child_id, child_graph_id, entries, edges);
+        }
+    }
+}
+
+fn node_attrs(node: &Node, workspace: Option<&WorkspaceResolved>) -> String {
+    let mut label = match node.kind {
+        NodeKind::Dir => format!("{}/", node.name),
+        NodeKind::File => node.name.clone(),
+        NodeKind::Error => match &node.error {
+            Some(err) => format!("{} ({})", node.name, err),
+            None => node.name.clone(),
+        },
+    };
+    if let Some(workspace) = workspace {
+        label.push_str(&format!("\n[workspace: {}]", workspace.kind.label()));
+    }
+    if let Some(module) = &node.module {
+        label.push_str(&format!("\n{}", module.kind.tag()));
+        if let Some(summary) = &module.summary {
+            label.push_str(&format!(" {summary}"));
+        }
+    }
+
+    let mut attrs = format!("label=\"{}\"", escape(&label));
+    match (&node.module, node.kind) {
+        (Some(module), _) => {
+            attrs.push_str(&format!(
+                ", style=\"filled,bold\", fillcolor=\"{}\"",
+                kind_color(module.kind)
+            ));
+        }
+        (None, NodeKind::Dir) => attrs.push_str(", shape=folder"),
+        (None, NodeKind::File) => attrs.push_str(", shape=note"),
+        (None, NodeKind::Error) => attrs.push_str(", shape=note, color=\"red\""),
+    }
+    attrs
+}
+
+fn kind_color(kind: ModuleKind) -> &'static str {
+    match kind {
+        ModuleKind::Node => "#c5e1a5",
+        ModuleKind::Python => "#bbdefb",
+        ModuleKind::Rust => "#ffccbc",
+        ModuleKind::Go => "#b2ebf2",
+        ModuleKind::Java => "#ffe0b2",
+        ModuleKind::DotNet => "#d1c4e9",
+        ModuleKind::Bazel => "#c8e6c9",
+        ModuleKind::Ruby => "#ffcdd2",
+        ModuleKind::Unknown => "#eeeeee",
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
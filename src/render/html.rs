@@ -0,0 +1,221 @@
+use crate::config::Config;
+use crate::model::{Tree, WorkspaceResolved};
+use crate::render::json::render_json_compact;
+
+/// Renders a self-contained HTML page that embeds the JSON document and
+/// browses it with inline script: collapsible directories, a search box and
+/// "show more" buttons in place of the `max_children` cut-off.
+pub fn render_html(tree: &Tree, workspace: Option<&WorkspaceResolved>, config: &Config) -> String {
+    let title = format!("{} - smarttree", tree.nodes[tree.root].name);
+    // `<` only occurs inside JSON strings, so escaping it keeps the payload
+    // valid JSON while making `</script>` impossible inside the data block.
+    let data = render_json_compact(tree, workspace, config).replace('<', "\\u003c");
+
+    fill_template(
+        TEMPLATE,
+        &[
+            ("{{TITLE}}", &escape_html(&title)),
+            ("{{MAX_CHILDREN}}", &config.max_children.to_string()),
+            ("{{DATA}}", &data),
+        ],
+    )
+}
+
+/// Replaces each placeholder in `template` in a single pass, so text that was
+/// substituted in (such as a directory name containing `{{DATA}}`) is never
+/// scanned for placeholders again.
+fn fill_template(template: &str, values: &[(&str, &str)]) -> String {
+    let mut filled = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        filled.push_str(&rest[..start]);
+        rest = &rest[start..];
+        match values.iter().find(|(key, _)| rest.starts_with(key)) {
+            Some((key, value)) => {
+                filled.push_str(value);
+                rest = &rest[key.len()..];
+            }
+            None => {
+                filled.push_str("{{");
+                rest = &rest[2..];
+            }
+        }
+    }
+    filled.push_str(rest);
+    filled
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+const TEMPLATE: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<meta name="generator" content="smarttree">
+<title>{{TITLE}}</title>
+<style>
+  body { font: 14px/1.5 ui-monospace, SFMono-Regular, Menlo, Consolas, monospace; margin: 1.5rem; color: #1f2328; }
+  header { display: flex; gap: 1rem; align-items: center; margin-bottom: 1rem; }
+  #search { flex: 1; max-width: 32rem; padding: 0.3rem 0.5rem; font: inherit; }
+  ul { list-style: none; margin: 0; padding-left: 1.25rem; }
+  #tree > ul { padding-left: 0; }
+  summary { cursor: pointer; }
+  .file { padding-left: 1rem; }
+  .badge { display: inline-block; margin-left: 0.5rem; padding: 0 0.35rem; border-radius: 0.3rem; font-size: 0.85em; background: #eaeef2; }
+  .kind-node { background: #c5e1a5; }
+  .kind-python { background: #bbdefb; }
+  .kind-rust { background: #ffccbc; }
+  .kind-go { background: #b2ebf2; }
+  .kind-java { background: #ffe0b2; }
+  .kind-dotnet { background: #d1c4e9; }
+  .kind-bazel { background: #c8e6c9; }
+  .kind-ruby { background: #ffcdd2; }
+  .workspace { background: #fff8c5; }
+  .summary { margin-left: 0.5rem; color: #656d76; }
+  .error { color: #cf222e; }
+  .more { margin-left: 1rem; font: inherit; cursor: pointer; }
+  .match > .label .name, .match > summary .name { background: #fff8c5; }
+  .notice { margin-top: 1rem; color: #656d76; }
+</style>
+</head>
+<body>
+<header>
+  <strong id="title"></strong>
+  <input id="search" type="search" placeholder="Filter by name, path or summary" autocomplete="off">
+</header>
+<main id="tree"></main>
+<p id="notice" class="notice"></p>
+<script id="smarttree-data" type="application/json">{{DATA}}</script>
+<script>
+(function () {
+  "use strict";
+  var data = JSON.parse(document.getElementById("smarttree-data").textContent);
+  var maxChildren = {{MAX_CHILDREN}};
+  var treeEl = document.getElementById("tree");
+
+  function el(tag, className, text) {
+    var node = document.createElement(tag);
+    if (className) node.className = className;
+    if (text !== undefined) node.textContent = text;
+    return node;
+  }
+
+  function label(node, isRoot) {
+    var span = el("span", "label");
+    var name = node.kind === "dir" ? node.name + "/" : node.name;
+    span.appendChild(el("span", node.kind === "error" ? "name error" : "name", name));
+    span.title = node.path || ".";
+    if (isRoot && data.workspace) {
+      span.appendChild(el("span", "badge workspace", "workspace: " + data.workspace.kind));
+    }
+    if (node.module) {
+      span.appendChild(el("span", "badge kind-" + node.module.kind, node.module.tag));
+      if (node.module.summary) span.appendChild(el("span", "summary", node.module.summary));
+    }
+    if (node.error) span.appendChild(el("span", "summary error", "(" + node.error + ")"));
+    return span;
+  }
+
+  function renderChildren(list, children, query) {
+    var limit = query ? children.length : Math.min(children.length, maxChildren);
+    for (var i = 0; i < limit; i++) list.appendChild(renderNode(children[i], query, false));
+    if (limit < children.length) {
+      var item = el("li");
+      var button = el("button", "more", "show " + (children.length - limit) + " more");
+      button.addEventListener("click", function () {
+        item.remove();
+        for (var j = limit; j < children.length; j++) list.appendChild(renderNode(children[j], query, false));
+      });
+      item.appendChild(button);
+      list.appendChild(item);
+    }
+  }
+
+  function renderNode(node, query, isRoot) {
+    var item = el("li");
+    if (query && matches(node, query)) item.className = "match";
+    if (node.kind === "dir" && node.children.length) {
+      var details = el("details");
+      details.open = isRoot || !!query;
+      var summary = el("summary");
+      summary.appendChild(label(node, isRoot));
+      details.appendChild(summary);
+      var list = el("ul");
+      details.appendChild(list);
+      if (details.open) {
+        renderChildren(list, node.children, query);
+      } else {
+        details.addEventListener("toggle", function once() {
+          details.removeEventListener("toggle", once);
+          renderChildren(list, node.children, query);
+        });
+      }
+      item.appendChild(details);
+    } else {
+      var leaf = label(node, isRoot);
+      leaf.classList.add("file");
+      item.appendChild(leaf);
+    }
+    return item;
+  }
+
+  function matches(node, query) {
+    var haystack = [node.name, node.path];
+    if (node.module && node.module.summary) haystack.push(node.module.summary);
+    return haystack.join("\n").toLowerCase().indexOf(query) !== -1;
+  }
+
+  function prune(node, query) {
+    var children = [];
+    for (var i = 0; i < node.children.length; i++) {
+      var child = prune(node.children[i], query);
+      if (child) children.push(child);
+    }
+    if (!children.length && !matches(node, query)) return null;
+    var copy = Object.assign({}, node);
+    copy.children = children;
+    return copy;
+  }
+
+  function render(query) {
+    treeEl.textContent = "";
+    var root = query ? prune(data.root, query) || Object.assign({}, data.root, { children: [] }) : data.root;
+    var list = el("ul");
+    list.appendChild(renderNode(root, query, true));
+    treeEl.appendChild(list);
+  }
+
+  document.getElementById("title").textContent = data.root.name + "/";
+  if (data.truncated) {
+    document.getElementById("notice").textContent =
+      "... (truncated after reaching max-items=" + data.max_items + ")";
+  }
+  document.getElementById("search").addEventListener("input", function (event) {
+    render(event.target.value.trim().toLowerCase());
+  });
+  render("");
+})();
+</script>
+</body>
+</html>
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn substituted_values_are_not_filled_again() {
+        let filled = fill_template(
+            "<title>{{TITLE}}</title>{{DATA}}",
+            &[("{{TITLE}}", "{{DATA}} - smarttree"), ("{{DATA}}", "{}")],
+        );
+        assert_eq!(filled, "<title>{{DATA}} - smarttree</title>{}");
+    }
+}
@@ -0,0 +1,315 @@
+//! JSON output.
+//!
+//! The document is versioned by [`SCHEMA_VERSION`]; new optional fields may be
+//! added within a version, while removals or changes in meaning bump it.
+//!
+//! ```text
+//! {
+//!   "schema_version": 1,
+//!   "root_path": "<path as given on the command line>",
+//!   "lens": "module" | "files" | "deps" | "owners" | "size" | "churn" | "entrypoints" | "languages" | "tests" | "docs" | "infra",
+//!   "filtered": bool,            // false when --unfiltered was passed
+//!   "truncated": bool,           // walk stopped at max_items
+//!   "truncated_at": number,      // items visited before stopping
+//!   "max_items": number,
+//!   "workspace": null | { "kind": "pnpm" | ..., "package_roots": ["apps/web", ...] },
+//!   "root": Node
+//! }
+//!
+//! Node = {
+//!   "name": string,
+//!   "path": string,              // relative to the root, '/'-separated, "" for the root
+//!   "kind": "dir" | "file" | "error",
+//!   "error": null | string,
+//!   "module": null | Module,
+//!   "owners": [string],          // owners lens only, omitted when empty
+//!   "size": { "bytes": number, "files": number, "partial"?: true },  // size lens or --sort size
+//!   "churn": { "commits": number, "added": number, "deleted": number, "hot": bool },  // churn lens only
+//!   "languages": [{ "language": string, "files": number, "lines": number }],  // languages lens only
+//!   "languages_partial"?: true,  // languages lens, when counting stopped at --max-items
+//!   "infra": [{ "kind": "docker" | "compose" | "k8s" | "helm" | "terraform" | "workflow",
+//!               "path": string, "detail": null | string }],  // infra lens only, paths from the root
+//!   "children": [Node]
+//! }
+//!
+//! Module = {
+//!   "kind": "node" | ...,
+//!   "tag": "[node]",
+//!   "summary": null | string,
+//!   "markers": [string],
+//!   "dependencies": [string],    // deps lens only, omitted when empty
+//!   "dependents": [string],      // deps lens only, omitted when empty
+//!   "affected": "changed" | "dependent",  // --changed-since only, omitted otherwise
+//!   "entrypoints": [{ "kind": "bin" | "main" | "export" | "script", "name": string, "target": null | string }]
+//!                                // entrypoints lens only, omitted when empty
+//!   "tests": { "files": number, "locations": [string], "partial"?: true },  // tests lens only
+//!   "docs": { "readme": null | string, "title": null | string, "changelog": null | string,
+//!             "docs_dir": null | string, "license": null | string }  // docs lens only
+//! }
+//! ```
+//!
+//! Children follow the same ordering and lens filtering as the text output,
+//! but `max_children` is not applied: structured output always lists every
+//! selected child.
+
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::model::{Lens, ModuleInfo, Tree, WorkspaceResolved};
+use crate::render::select::{path_to_slash, Selector};
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct JsonDocument<'a> {
+    schema_version: u32,
+    root_path: String,
+    lens: Lens,
+    filtered: bool,
+    truncated: bool,
+    truncated_at: usize,
+    max_items: usize,
+    workspace: Option<JsonWorkspace>,
+    root: JsonNode<'a>,
+}
+
+#[derive(Serialize)]
+struct JsonWorkspace {
+    kind: &'static str,
+    package_roots: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct JsonNode<'a> {
+    name: &'a str,
+    path: String,
+    kind: &'static str,
+    error: Option<&'a str>,
+    module: Option<JsonModule<'a>>,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    owners: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size: Option<JsonSize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    churn: Option<JsonChurn>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    languages: Vec<JsonLanguage>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    languages_partial: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    infra: Vec<JsonInfra<'a>>,
+    children: Vec<JsonNode<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonSize {
+    bytes: u64,
+    files: u64,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    partial: bool,
+}
+
+#[derive(Serialize)]
+struct JsonChurn {
+    commits: u64,
+    added: u64,
+    deleted: u64,
+    hot: bool,
+}
+
+#[derive(Serialize)]
+struct JsonInfra<'a> {
+    kind: &'static str,
+    path: String,
+    detail: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct JsonLanguage {
+    language: &'static str,
+    files: u64,
+    lines: u64,
+}
+
+#[derive(Serialize)]
+pub(crate) struct JsonModule<'a> {
+    kind: &'static str,
+    tag: &'static str,
+    summary: Option<&'a str>,
+    markers: &'a [String],
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    dependencies: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    dependents: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    affected: Option<&'static str>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    entrypoints: Vec<JsonEntrypoint<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tests: Option<JsonTests<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    docs: Option<JsonDocs<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonDocs<'a> {
+    readme: Option<&'a str>,
+    title: Option<&'a str>,
+    changelog: Option<&'a str>,
+    docs_dir: Option<&'a str>,
+    license: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct JsonTests<'a> {
+    files: u64,
+    locations: &'a [String],
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    partial: bool,
+}
+
+#[derive(Serialize)]
+struct JsonEntrypoint<'a> {
+    kind: &'static str,
+    name: &'a str,
+    target: Option<&'a str>,
+}
+
+impl<'a> From<&'a ModuleInfo> for JsonModule<'a> {
+    fn from(module: &'a ModuleInfo) -> Self {
+        JsonModule {
+            kind: module.kind.label(),
+            tag: module.kind.tag(),
+            summary: module.summary.as_deref(),
+            markers: &module.markers,
+            dependencies: module
+                .dependencies
+                .iter()
+                .map(|p| path_to_slash(p))
+                .collect(),
+            dependents: module.dependents.iter().map(|p| path_to_slash(p)).collect(),
+            affected: module.affected.map(|a| a.label()),
+            entrypoints: module
+                .entrypoints
+                .iter()
+                .map(|entry| JsonEntrypoint {
+                    kind: entry.kind.label(),
+                    name: &entry.name,
+                    target: entry.target.as_deref(),
+                })
+                .collect(),
+            tests: module.tests.as_ref().map(|tests| JsonTests {
+                files: tests.files,
+                locations: &tests.locations,
+                partial: tests.partial,
+            }),
+            docs: module.docs.as_ref().map(|docs| JsonDocs {
+                readme: docs.readme.as_deref(),
+                title: docs.title.as_deref(),
+                changelog: docs.changelog.as_deref(),
+                docs_dir: docs.docs_dir.as_deref(),
+                license: docs.license.as_deref(),
+            }),
+        }
+    }
+}
+
+pub fn render_json(tree: &Tree, workspace: Option<&WorkspaceResolved>, config: &Config) -> String {
+    serde_json::to_string_pretty(&build_document(tree, workspace, config)).unwrap_or_default()
+}
+
+/// Compact single-line form of the document, for embedding in other outputs.
+pub(crate) fn render_json_compact(
+    tree: &Tree,
+    workspace: Option<&WorkspaceResolved>,
+    config: &Config,
+) -> String {
+    serde_json::to_string(&build_document(tree, workspace, config)).unwrap_or_default()
+}
+
+fn build_document<'a>(
+    tree: &'a Tree,
+    workspace: Option<&WorkspaceResolved>,
+    config: &Config,
+) -> JsonDocument<'a> {
+    let selector = if config.unfiltered {
+        Selector::unfiltered(tree)
+    } else {
+        Selector::new(tree, config)
+    };
+
+    let workspace = workspace.map(|w| {
+        let mut package_roots: Vec<String> =
+            w.package_roots.iter().map(|p| path_to_slash(p)).collect();
+        package_roots.sort();
+        JsonWorkspace {
+            kind: w.kind.label(),
+            package_roots,
+        }
+    });
+
+    JsonDocument {
+        schema_version: SCHEMA_VERSION,
+        root_path: tree.root_path.to_string_lossy().into_owned(),
+        lens: config.lens,
+        filtered: !config.unfiltered,
+        truncated: tree.truncated,
+        truncated_at: tree.truncated_at,
+        max_items: config.max_items,
+        workspace,
+        root: build_node(tree, &selector, tree.root),
+    }
+}
+
+fn build_node<'a>(tree: &'a Tree, selector: &Selector<'_>, node_id: usize) -> JsonNode<'a> {
+    let node = &tree.nodes[node_id];
+    let children = if node.is_dir() {
+        selector
+            .children(node_id)
+            .into_iter()
+            .map(|child_id| build_node(tree, selector, child_id))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    JsonNode {
+        name: &node.name,
+        path: path_to_slash(&node.rel_path),
+        kind: node.kind.label(),
+        error: node.error.as_deref(),
+        module: node.module.as_ref().map(JsonModule::from),
+        owners: &node.owners,
+        size: node.size.map(|size| JsonSize {
+            bytes: size.bytes,
+            files: size.files,
+            partial: size.partial,
+        }),
+        churn: node.churn.map(|churn| JsonChurn {
+            commits: churn.commits,
+            added: churn.added,
+            deleted: churn.deleted,
+            hot: churn.hot,
+        }),
+        languages: node
+            .languages
+            .iter()
+            .map(|l| JsonLanguage {
+                language: l.language,
+                files: l.files,
+                lines: l.lines,
+            })
+            .collect(),
+        languages_partial: node.languages_partial,
+        infra: node
+            .infra
+            .iter()
+            .map(|file| JsonInfra {
+                kind: file.kind.label(),
+                path: path_to_slash(&file.path),
+                detail: file.detail.as_deref(),
+            })
+            .collect(),
+        children,
+    }
+}
@@ -1,3 +1,7 @@
+use crate::config::Config;
+use crate::model::{Node, NodeKind, Tree, WorkspaceResolved};
+use crate::render::select::{path_to_slash, Selector};
+
 pub fn render_md(text: &str) -> String {
     let mut out = String::new();
     out.push_str("```text\n");
@@ -6,3 +10,149 @@ pub fn render_md(text: &str) -> String {
     out.push_str("```");
     out
 }
+
+/// Renders the tree as nested Markdown bullet lists, linking every entry to
+/// its path relative to the scanned root.
+pub fn render_md_list(
+    tree: &Tree,
+    workspace: Option<&WorkspaceResolved>,
+    config: &Config,
+) -> String {
+    let selector = Selector::new(tree, config);
+    let mut lines = Vec::new();
+
+    let root = &tree.nodes[tree.root];
+    let root_href = if root.kind == NodeKind::Dir {
+        "./".to_string()
+    } else {
+        encode_link(&root.name)
+    };
+    lines.push(format!(
+        "- {}",
+        format_node_label(root, &root_href, workspace)
+    ));
+    render_children(tree, &selector, config, tree.root, 1, &mut lines);
+
+    if tree.truncated {
+        lines.push(String::new());
+        lines.push(format!(
+            "... (truncated after reaching max-items={})",
+            config.max_items
+        ));
+    }
+
+    lines.join("\n")
+}
+
+fn render_children(
+    tree: &Tree,
+    selector: &Selector<'_>,
+    config: &Config,
+    node_id: usize,
+    depth: usize,
+    lines: &mut Vec<String>,
+) {
+    let children = selector.children(node_id);
+    let indent = "  ".repeat(depth);
+    let omitted = children.len().saturating_sub(config.max_children);
+
+    for child_id in children.iter().take(config.max_children) {
+        let child = &tree.nodes[*child_id];
+        let href = link_target(child);
+        lines.push(format!(
+            "{indent}- {}",
+            format_node_label(child, &href, None)
+        ));
+        if child.kind == NodeKind::Dir {
+            render_children(tree, selector, config, *child_id, depth + 1, lines);
+        }
+    }
+
+    if omitted > 0 {
+        lines.push(format!("{indent}- ... ({omitted} more)"));
+    }
+}
+
+fn link_target(node: &Node) -> String {
+    let path = encode_link(&path_to_slash(&node.rel_path));
+    if node.kind == NodeKind::Dir {
+        format!("{path}/")
+    } else {
+        path
+    }
+}
+
+fn format_node_label(node: &Node, href: &str, workspace: Option<&WorkspaceResolved>) -> String {
+    let mut label = match node.kind {
+        NodeKind::Dir => format!("[{}/]({href})", escape_text(&node.name)),
+        NodeKind::File => format!("[{}]({href})", escape_text(&node.name)),
+        NodeKind::Error => match &node.error {
+            Some(err) => format!("{} ({})", escape_text(&node.name), escape_text(err)),
+            None => escape_text(&node.name),
+        },
+    };
+
+    if let Some(workspace) = workspace {
+        label.push_str(&format!(" `[workspace: {}]`", workspace.kind.label()));
+    }
+
+    if let Some(module) = &node.module {
+        label.push_str(&format!(" `{}`", module.kind.tag()));
+        if let Some(summary) = &module.summary {
+            label.push_str(&format!(" {}", escape_text(summary)));
+        }
+    }
+
+    label
+}
+
+fn escape_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        if matches!(
+            ch,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|'
+        ) {
+            out.push('\\');
+        }
+        out.push(ch);
+    }
+    out
+}
+
+fn encode_link(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    for ch in path.chars() {
+        match ch {
+            ' ' => out.push_str("%20"),
+            '%' => out.push_str("%25"),
+            '(' => out.push_str("%28"),
+            ')' => out.push_str("%29"),
+            '<' => out.push_str("%3C"),
+            '>' => out.push_str("%3E"),
+            '#' => out.push_str("%23"),
+            '?' => out.push_str("%3F"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_markdown_in_names() {
+        assert_eq!(escape_text("__init__.py"), "\\_\\_init\\_\\_.py");
+        assert_eq!(escape_text("a [b]"), "a \\[b\\]");
+    }
+
+    #[test]
+    fn encodes_link_targets() {
+        assert_eq!(
+            encode_link("my dir/file (1).md"),
+            "my%20dir/file%20%281%29.md"
+        );
+    }
+}
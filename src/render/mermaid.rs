@@ -0,0 +1,111 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use crate::discover::workspace::group_dir;
+use crate::model::{Node, NodeKind, Tree, WorkspaceResolved};
+use crate::render::select::Selector;
+
+/// Renders the root, the workspace group directories and every annotated
+/// module as a Mermaid flowchart. Each graph node links to its nearest
+/// ancestor that is also part of the graph. Node ids follow render order so
+/// the output is stable across filesystems.
+pub fn render_mermaid(tree: &Tree, workspace: Option<&WorkspaceResolved>) -> String {
+    let mut graph = Graph {
+        tree,
+        selector: Selector::unfiltered(tree),
+        groups: group_dirs(tree),
+        lines: vec!["flowchart TD".to_string()],
+        edges: Vec::new(),
+        next_id: 0,
+    };
+
+    let root = &tree.nodes[tree.root];
+    let root_id = graph.add_node(&format!("[\"{}\"]", node_label(root, workspace)));
+    graph.visit(tree.root, root_id);
+
+    let Graph {
+        mut lines, edges, ..
+    } = graph;
+    lines.extend(edges);
+    lines.join("\n")
+}
+
+struct Graph<'a> {
+    tree: &'a Tree,
+    selector: Selector<'a>,
+    groups: HashSet<PathBuf>,
+    lines: Vec<String>,
+    edges: Vec<String>,
+    next_id: usize,
+}
+
+impl<'a> Graph<'a> {
+    fn add_node(&mut self, shape: &str) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.lines.push(format!("    n{id}{shape}"));
+        id
+    }
+
+    fn visit(&mut self, node_id: usize, graph_parent: usize) {
+        for child_id in self.selector.children(node_id) {
+            let child = &self.tree.nodes[child_id];
+            if child.kind != NodeKind::Dir {
+                continue;
+            }
+            let shape = if child.module.is_some() {
+                Some(format!("[\"{}\"]", node_label(child, None)))
+            } else if self.groups.contains(&child.rel_path) {
+                Some(format!("([\"{}\"])", node_label(child, None)))
+            } else {
+                None
+            };
+            let parent = match shape {
+                Some(shape) => {
+                    let id = self.add_node(&shape);
+                    self.edges.push(format!("    n{graph_parent} --> n{id}"));
+                    id
+                }
+                None => graph_parent,
+            };
+            self.visit(child_id, parent);
+        }
+    }
+}
+
+/// Group directories (`apps/`, `packages/`, ...) that hold modules, found the
+/// same way as the DOT renderer's clusters.
+fn group_dirs(tree: &Tree) -> HashSet<PathBuf> {
+    tree.nodes
+        .iter()
+        .filter(|node| node.module.is_some())
+        .filter_map(|node| group_dir(&node.rel_path))
+        .map(PathBuf::from)
+        .collect()
+}
+
+fn node_label(node: &Node, workspace: Option<&WorkspaceResolved>) -> String {
+    let mut label = escape_label(&format!("{}/", node.name));
+    let mut details = Vec::new();
+    if let Some(workspace) = workspace {
+        details.push(format!("[workspace: {}]", workspace.kind.label()));
+    }
+    if let Some(module) = &node.module {
+        details.push(module.kind.tag().to_string());
+        if let Some(summary) = &module.summary {
+            details.push(summary.clone());
+        }
+    }
+    if !details.is_empty() {
+        label.push_str("<br/>");
+        label.push_str(&escape_label(&details.join(" ")));
+    }
+    label
+}
+
+fn escape_label(label: &str) -> String {
+    label
+        .replace('"', "#quot;")
+        .replace('<', "#lt;")
+        .replace('>', "#gt;")
+}
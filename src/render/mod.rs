@@ -1,14 +1,36 @@
+pub mod context;
+pub mod dot;
+pub mod html;
+pub mod json;
 pub mod md;
+pub mod mermaid;
+pub mod ndjson;
+mod select;
+pub mod style;
+pub mod table;
 pub mod text;
 
 use crate::config::Config;
-use crate::model::{Format, Tree, WorkspaceResolved};
+use crate::model::{Format, MdStyle, Tree, WorkspaceResolved};
+use crate::render::style::Style;
+use crate::render::table::TableStyle;
 
 pub fn render(tree: &Tree, workspace: Option<&WorkspaceResolved>, config: &Config) -> String {
-    let text = text::render_text(tree, workspace, config);
     let mut output = match config.format {
-        Format::Text => text,
-        Format::Md => md::render_md(&text),
+        Format::Text => text::render_text_styled(tree, workspace, config, Style::detect(config)),
+        Format::Md => match config.md_style {
+            MdStyle::List => md::render_md_list(tree, workspace, config),
+            MdStyle::Fence => md::render_md(&text::render_text(tree, workspace, config)),
+        },
+        Format::Json => json::render_json(tree, workspace, config),
+        Format::Mermaid => mermaid::render_mermaid(tree, workspace),
+        Format::Dot => dot::render_dot(tree, workspace, config),
+        Format::Html => html::render_html(tree, workspace, config),
+        Format::Ndjson => ndjson::render_ndjson(tree, workspace),
+        Format::Context => context::render_context(tree, workspace, config),
+        Format::Csv => table::render_table(tree, workspace, config, TableStyle::Csv),
+        Format::Tsv => table::render_table(tree, workspace, config, TableStyle::Tsv),
+        Format::Mdtable => table::render_table(tree, workspace, config, TableStyle::Markdown),
     };
     if !output.ends_with('\n') {
         output.push('\n');
@@ -0,0 +1,256 @@
+//! Newline-delimited JSON output.
+//!
+//! Every walked entry produces one `node` record, followed by a single `end`
+//! record once the walk finishes:
+//!
+//! ```text
+//! {"type":"node","path":"apps/web","name":"web","kind":"dir","depth":2,"module":{...}|null,"error":null}
+//! {"type":"end","schema_version":1,"workspace":"pnpm"|null,"count":17,"truncated":false}
+//! ```
+//!
+//! `module` has the same shape as in the JSON output. Lens filtering does not
+//! apply: every walked entry is reported.
+//!
+//! [`stream`] writes records while the walk is still running. Files and
+//! errors are written as soon as they are seen; a directory is written once
+//! the walk leaves it, because its module info depends on the marker files
+//! inside it. Consumers that stop reading early simply end the walk.
+
+use std::fs;
+use std::io::{self, Write};
+use std::ops::ControlFlow;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::discover::markers::classify_markers;
+use crate::discover::summary;
+use crate::discover::walk::{self, WalkEntry};
+use crate::discover::workspace::{self, PackageRootMatcher};
+use crate::model::{ModuleInfo, NodeKind, Tree, WorkspaceKind, WorkspaceResolved};
+use crate::render::json::{JsonModule, SCHEMA_VERSION};
+use crate::render::select::{path_to_slash, Selector};
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum Record<'a> {
+    Node {
+        path: String,
+        name: &'a str,
+        kind: &'static str,
+        depth: usize,
+        module: Option<Box<JsonModule<'a>>>,
+        error: Option<&'a str>,
+    },
+    End {
+        schema_version: u32,
+        workspace: Option<&'static str>,
+        count: usize,
+        truncated: bool,
+    },
+}
+
+fn node_record<'a>(
+    rel_path: &std::path::Path,
+    name: &'a str,
+    kind: NodeKind,
+    module: Option<&'a ModuleInfo>,
+    error: Option<&'a str>,
+) -> Record<'a> {
+    Record::Node {
+        path: path_to_slash(rel_path),
+        name,
+        kind: kind.label(),
+        depth: rel_path.components().count(),
+        module: module.map(|module| Box::new(JsonModule::from(module))),
+        error,
+    }
+}
+
+fn to_line(record: &Record<'_>) -> String {
+    serde_json::to_string(record).unwrap_or_default()
+}
+
+/// Renders an already discovered tree as NDJSON, in pre-order.
+pub fn render_ndjson(tree: &Tree, workspace: Option<&WorkspaceResolved>) -> String {
+    let selector = Selector::unfiltered(tree);
+    let mut lines = Vec::new();
+    let mut stack = vec![tree.root];
+    while let Some(node_id) = stack.pop() {
+        let node = &tree.nodes[node_id];
+        lines.push(to_line(&node_record(
+            &node.rel_path,
+            &node.name,
+            node.kind,
+            node.module.as_ref(),
+            node.error.as_deref(),
+        )));
+        if node.is_dir() {
+            stack.extend(selector.children(node_id).into_iter().rev());
+        }
+    }
+    lines.push(to_line(&Record::End {
+        schema_version: SCHEMA_VERSION,
+        workspace: workspace.map(|w| w.kind.label()),
+        count: tree.truncated_at,
+        truncated: tree.truncated,
+    }));
+    lines.join("\n")
+}
+
+struct OpenDir {
+    name: String,
+    rel_path: PathBuf,
+    files: Vec<String>,
+}
+
+struct Streamer<'a, W: Write> {
+    out: &'a mut W,
+    config: &'a Config,
+    matcher: Option<PackageRootMatcher>,
+    workspace: Option<WorkspaceKind>,
+    open: Vec<OpenDir>,
+    io_error: Option<io::Error>,
+}
+
+impl<'a, W: Write> Streamer<'a, W> {
+    fn write(&mut self, record: &Record<'_>) -> ControlFlow<()> {
+        match writeln!(self.out, "{}", to_line(record)) {
+            Ok(()) => ControlFlow::Continue(()),
+            Err(err) => {
+                self.io_error = Some(err);
+                ControlFlow::Break(())
+            }
+        }
+    }
+
+    fn visit(&mut self, entry: WalkEntry) -> ControlFlow<()> {
+        while self
+            .open
+            .last()
+            .is_some_and(|dir| !entry.rel_path.starts_with(&dir.rel_path))
+        {
+            self.close_dir()?;
+        }
+
+        if let Some(parent) = self.open.last_mut() {
+            if entry.kind != NodeKind::Dir && entry.rel_path.parent() == Some(&parent.rel_path) {
+                parent.files.push(entry.name.clone());
+            }
+        }
+
+        if entry.kind == NodeKind::Dir {
+            self.open.push(OpenDir {
+                name: entry.name,
+                rel_path: entry.rel_path,
+                files: Vec::new(),
+            });
+            return ControlFlow::Continue(());
+        }
+
+        let record = node_record(
+            &entry.rel_path,
+            &entry.name,
+            entry.kind,
+            None,
+            entry.error.as_deref(),
+        );
+        self.write(&record)
+    }
+
+    fn close_dir(&mut self) -> ControlFlow<()> {
+        let Some(dir) = self.open.pop() else {
+            return ControlFlow::Continue(());
+        };
+        let module = self.module_for(&dir);
+        let record = node_record(
+            &dir.rel_path,
+            &dir.name,
+            NodeKind::Dir,
+            module.as_ref(),
+            None,
+        );
+        self.write(&record)
+    }
+
+    fn module_for(&self, dir: &OpenDir) -> Option<ModuleInfo> {
+        let (kind, markers) = classify_markers(
+            &self.config.root.join(&dir.rel_path),
+            dir.files.iter().map(String::as_str),
+            self.workspace,
+        )?;
+        let is_root = dir.rel_path.as_os_str().is_empty();
+        if !is_root {
+            if let Some(matcher) = &self.matcher {
+                if !matcher.is_within_package(&dir.rel_path, &markers) {
+                    return None;
+                }
+            }
+        }
+        let module_path = self.config.root.join(&dir.rel_path);
+        Some(ModuleInfo::new(
+            kind,
+            summary::read_summary(&module_path, kind),
+            markers,
+        ))
+    }
+}
+
+/// Walks `config.root` and writes NDJSON records to `out` as entries are
+/// discovered. A closed output (for example `| head`) ends the walk quietly.
+pub fn stream<W: Write>(config: &Config, out: &mut W) -> Result<()> {
+    if fs::metadata(&config.root)
+        .map(|m| m.is_file())
+        .unwrap_or(false)
+    {
+        let tree = walk::build_tree(config)?;
+        return finish(writeln!(out, "{}", render_ndjson(&tree, None)));
+    }
+
+    let info = workspace::detect_workspace(&config.root)?;
+    let matcher = info
+        .as_ref()
+        .map(|info| PackageRootMatcher::new(info, &config.root))
+        .transpose()?;
+    let root_name = walk::display_root_name(&config.root);
+
+    let mut streamer = Streamer {
+        out,
+        config,
+        matcher,
+        workspace: info.as_ref().map(|info| info.kind),
+        open: vec![OpenDir {
+            name: root_name,
+            rel_path: PathBuf::new(),
+            files: Vec::new(),
+        }],
+        io_error: None,
+    };
+
+    let summary = walk::walk(config, |entry| streamer.visit(entry))?;
+    while streamer.io_error.is_none() && !streamer.open.is_empty() {
+        let _ = streamer.close_dir();
+    }
+    if streamer.io_error.is_none() {
+        let _ = streamer.write(&Record::End {
+            schema_version: SCHEMA_VERSION,
+            workspace: info.map(|i| i.kind.label()),
+            count: summary.count,
+            truncated: summary.truncated,
+        });
+    }
+
+    match streamer.io_error {
+        Some(err) => finish(Err(err)),
+        None => finish(streamer.out.flush()),
+    }
+}
+
+fn finish(result: io::Result<()>) -> Result<()> {
+    match result {
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        other => Ok(other?),
+    }
+}
@@ -0,0 +1,151 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::config::Config;
+use crate::model::{NodeKind, SortOrder, Tree};
+
+pub(crate) fn path_to_slash(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Paths of the shown modules and all their ancestors. With `affected_only`,
+/// only modules in the `--changed-since` set are shown.
+fn compute_module_paths(tree: &Tree, affected_only: bool) -> HashSet<String> {
+    let mut paths = HashSet::new();
+    paths.insert(String::new());
+    for node in &tree.nodes {
+        let Some(module) = &node.module else {
+            continue;
+        };
+        if affected_only && module.affected.is_none() {
+            continue;
+        }
+        let mut current = node.rel_path.clone();
+        loop {
+            paths.insert(path_to_slash(&current));
+            if let Some(parent) = current.parent() {
+                current = parent.to_path_buf();
+            } else {
+                break;
+            }
+        }
+    }
+    paths
+}
+
+/// Decides which children of a node are visible under the configured lens,
+/// and in which order. Shared by every renderer so the views stay consistent.
+pub(crate) struct Selector<'a> {
+    tree: &'a Tree,
+    module_paths: HashSet<String>,
+    key_dirs: HashSet<String>,
+    filtered: bool,
+    affected_only: bool,
+    sort: SortOrder,
+    min_size: u64,
+}
+
+impl<'a> Selector<'a> {
+    pub(crate) fn new(tree: &'a Tree, config: &Config) -> Self {
+        let affected_only = config.changed_since.is_some();
+        let filtered = config.lens.filters_to_modules() || affected_only;
+        let module_paths = if filtered {
+            compute_module_paths(tree, affected_only)
+        } else {
+            HashSet::new()
+        };
+        let key_dirs: HashSet<String> = config.key_dirs.iter().cloned().collect();
+        Selector {
+            tree,
+            module_paths,
+            key_dirs,
+            filtered,
+            affected_only,
+            sort: config.sort,
+            min_size: config.min_size,
+        }
+    }
+
+    /// A selector that keeps every child, regardless of lens.
+    pub(crate) fn unfiltered(tree: &'a Tree) -> Self {
+        Selector {
+            tree,
+            module_paths: HashSet::new(),
+            key_dirs: HashSet::new(),
+            filtered: false,
+            affected_only: false,
+            sort: SortOrder::Name,
+            min_size: 0,
+        }
+    }
+
+    pub(crate) fn children(&self, node_id: usize) -> Vec<usize> {
+        let parent = &self.tree.nodes[node_id];
+        // Outside the `--changed-since` set, a module's key dirs and marker
+        // files are hidden along with the module itself.
+        let parent_is_module = parent
+            .module
+            .as_ref()
+            .is_some_and(|m| !self.affected_only || m.affected.is_some());
+        let marker_files: HashSet<String> = parent
+            .module
+            .as_ref()
+            .map(|m| m.markers.clone())
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+
+        let mut children: Vec<usize> = parent.children.clone();
+        children.retain(|child_id| {
+            let child = &self.tree.nodes[*child_id];
+            if child.size.is_some_and(|size| size.bytes < self.min_size) {
+                return false;
+            }
+            if !self.filtered {
+                return true;
+            }
+            let child_path = path_to_slash(&child.rel_path);
+            match child.kind {
+                NodeKind::Dir => {
+                    let is_key_dir = parent_is_module && self.key_dirs.contains(&child.name);
+                    let in_module_path = self.module_paths.contains(&child_path);
+                    is_key_dir || in_module_path
+                }
+                NodeKind::File | NodeKind::Error => {
+                    if parent_is_module {
+                        marker_files.contains(&child.name)
+                    } else {
+                        false
+                    }
+                }
+            }
+        });
+
+        children.sort_by(|a, b| {
+            let na = &self.tree.nodes[*a];
+            let nb = &self.tree.nodes[*b];
+            let by_size = match (self.sort, na.size, nb.size) {
+                (SortOrder::Size, Some(sa), Some(sb)) => sb.bytes.cmp(&sa.bytes),
+                _ => std::cmp::Ordering::Equal,
+            };
+            let ka = kind_rank(na.kind);
+            let kb = kind_rank(nb.kind);
+            by_size
+                .then_with(|| ka.cmp(&kb))
+                .then_with(|| na.name.to_lowercase().cmp(&nb.name.to_lowercase()))
+                .then_with(|| na.name.cmp(&nb.name))
+        });
+
+        children
+    }
+}
+
+fn kind_rank(kind: NodeKind) -> u8 {
+    match kind {
+        NodeKind::Dir => 0,
+        NodeKind::File | NodeKind::Error => 1,
+    }
+}
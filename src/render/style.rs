@@ -0,0 +1,132 @@
+use std::io::IsTerminal;
+use std::path::Path;
+
+use crate::config::Config;
+use crate::model::{ModuleKind, When};
+
+const ERROR: &str = "31";
+const DIM: &str = "2";
+const BOLD: &str = "1";
+const NOTICE: &str = "1;33";
+
+/// Terminal decorations applied by the text renderer. The default is plain
+/// output, which is what every non-terminal format uses.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Style {
+    color: bool,
+    hyperlinks: bool,
+}
+
+impl Style {
+    pub fn plain() -> Self {
+        Style::default()
+    }
+
+    /// Resolves `--color` and `--hyperlinks` against the environment: `auto`
+    /// colours only when stdout is a terminal and `NO_COLOR` is unset or
+    /// empty, and emits hyperlinks only when stdout is a terminal.
+    pub fn detect(config: &Config) -> Self {
+        let is_terminal = std::io::stdout().is_terminal();
+        let color = match config.color {
+            When::Always => true,
+            When::Never => false,
+            When::Auto => no_color_unset() && is_terminal,
+        };
+        let hyperlinks = match config.hyperlinks {
+            When::Always => true,
+            When::Never => false,
+            When::Auto => is_terminal,
+        };
+        Style { color, hyperlinks }
+    }
+
+    pub(crate) fn hyperlinks(&self) -> bool {
+        self.hyperlinks
+    }
+
+    /// Wraps `text` in an OSC 8 hyperlink to `url`.
+    pub(crate) fn link(&self, url: &str, text: &str) -> String {
+        if self.hyperlinks {
+            format!("\u{1b}]8;;{url}\u{1b}\\{text}\u{1b}]8;;\u{1b}\\")
+        } else {
+            text.to_string()
+        }
+    }
+
+    fn paint(&self, code: &str, text: &str) -> String {
+        if self.color && !text.is_empty() {
+            format!("\u{1b}[{code}m{text}\u{1b}[0m")
+        } else {
+            text.to_string()
+        }
+    }
+
+    pub(crate) fn module_tag(&self, kind: ModuleKind) -> String {
+        self.paint(&format!("1;{}", kind_color(kind)), kind.tag())
+    }
+
+    pub(crate) fn summary(&self, text: &str) -> String {
+        self.paint(DIM, text)
+    }
+
+    pub(crate) fn error(&self, text: &str) -> String {
+        self.paint(ERROR, text)
+    }
+
+    pub(crate) fn highlight(&self, text: &str) -> String {
+        self.paint(BOLD, text)
+    }
+
+    pub(crate) fn notice(&self, text: &str) -> String {
+        self.paint(NOTICE, text)
+    }
+}
+
+/// Builds a `file://` URL for an absolute path.
+pub(crate) fn file_url(path: &Path) -> String {
+    let slashed = path.to_string_lossy().replace('\\', "/");
+    let mut url = String::from("file://");
+    if !slashed.starts_with('/') {
+        url.push('/');
+    }
+    for byte in slashed.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' | b':' => {
+                url.push(byte as char)
+            }
+            _ => url.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    url
+}
+
+fn no_color_unset() -> bool {
+    std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+}
+
+fn kind_color(kind: ModuleKind) -> &'static str {
+    match kind {
+        ModuleKind::Node => "32",
+        ModuleKind::Python => "34",
+        ModuleKind::Rust => "33",
+        ModuleKind::Go => "36",
+        ModuleKind::Java => "35",
+        ModuleKind::DotNet => "95",
+        ModuleKind::Bazel => "92",
+        ModuleKind::Ruby => "31",
+        ModuleKind::Unknown => "37",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_url_encodes_path() {
+        assert_eq!(
+            file_url(Path::new("/home/me/my repo/a#b")),
+            "file:///home/me/my%20repo/a%23b"
+        );
+    }
+}
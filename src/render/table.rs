@@ -0,0 +1,141 @@
+use crate::config::Config;
+use crate::model::{Column, Node, Tree, WorkspaceResolved};
+use crate::render::select::path_to_slash;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TableStyle {
+    Csv,
+    Tsv,
+    Markdown,
+}
+
+/// Renders one row per annotated module, ordered by path, with the columns
+/// selected in `config.columns`. With `--changed-since`, only affected
+/// modules are listed.
+pub fn render_table(
+    tree: &Tree,
+    workspace: Option<&WorkspaceResolved>,
+    config: &Config,
+    style: TableStyle,
+) -> String {
+    let affected_only = config.changed_since.is_some();
+    let mut modules: Vec<&Node> = tree
+        .nodes
+        .iter()
+        .filter(|n| {
+            n.module
+                .as_ref()
+                .is_some_and(|m| !affected_only || m.affected.is_some())
+        })
+        .collect();
+    modules.sort_by_key(|node| path_to_slash(&node.rel_path));
+
+    let header: Vec<String> = config
+        .columns
+        .iter()
+        .map(|c| c.label().to_string())
+        .collect();
+    let mut lines = vec![format_row(&header, style)];
+    if style == TableStyle::Markdown {
+        lines.push(format!("|{}", " --- |".repeat(header.len())));
+    }
+
+    for node in modules {
+        let row: Vec<String> = config
+            .columns
+            .iter()
+            .map(|column| cell(node, *column, workspace))
+            .collect();
+        lines.push(format_row(&row, style));
+    }
+
+    lines.join("\n")
+}
+
+fn cell(node: &Node, column: Column, workspace: Option<&WorkspaceResolved>) -> String {
+    let Some(module) = &node.module else {
+        return String::new();
+    };
+    match column {
+        Column::Path => {
+            let path = path_to_slash(&node.rel_path);
+            if path.is_empty() {
+                ".".to_string()
+            } else {
+                path
+            }
+        }
+        Column::Kind => module.kind.label().to_string(),
+        Column::Summary => module.summary.clone().unwrap_or_default(),
+        Column::Markers => module.markers.join(";"),
+        Column::Workspace => match workspace {
+            Some(workspace) if owned_by(node, workspace) => workspace.kind.label().to_string(),
+            _ => String::new(),
+        },
+    }
+}
+
+/// A module belongs to the workspace when it is the root or sits inside one
+/// of its package roots.
+fn owned_by(node: &Node, workspace: &WorkspaceResolved) -> bool {
+    node.rel_path.as_os_str().is_empty()
+        || workspace
+            .package_roots
+            .iter()
+            .any(|root| node.rel_path.starts_with(root))
+}
+
+fn format_row(fields: &[String], style: TableStyle) -> String {
+    match style {
+        TableStyle::Csv => fields
+            .iter()
+            .map(|f| escape_csv(f))
+            .collect::<Vec<_>>()
+            .join(","),
+        TableStyle::Tsv => fields
+            .iter()
+            .map(|f| escape_tsv(f))
+            .collect::<Vec<_>>()
+            .join("\t"),
+        TableStyle::Markdown => {
+            let cells: Vec<String> = fields.iter().map(|f| escape_md_cell(f)).collect();
+            format!("| {} |", cells.join(" | "))
+        }
+    }
+}
+
+fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn escape_tsv(field: &str) -> String {
+    field
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+fn escape_md_cell(field: &str) -> String {
+    field
+        .replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace(['\r', '\n'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_fields_per_style() {
+        assert_eq!(escape_csv("a, \"b\""), "\"a, \"\"b\"\"\"");
+        assert_eq!(escape_csv("plain"), "plain");
+        assert_eq!(escape_tsv("a\tb\nc"), "a\\tb\\nc");
+        assert_eq!(escape_md_cell("a | b\nc"), "a \\| b c");
+    }
+}
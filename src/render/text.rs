@@ -1,8 +1,13 @@
-use std::collections::HashSet;
-use std::path::Path;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 use crate::config::Config;
-use crate::model::{Lens, NodeKind, Tree, WorkspaceResolved};
+use crate::model::{
+    ByteSize, DocsInfo, Entrypoint, EntrypointKind, InfraKind, LanguageCount, Lens, Node, NodeKind,
+    TestInfo, Tree, WorkspaceResolved,
+};
+use crate::render::select::{path_to_slash, Selector};
+use crate::render::style::{file_url, Style};
 
 #[derive(Clone, Copy)]
 struct TreeChars {
@@ -30,77 +35,192 @@ fn chars(unicode: bool) -> TreeChars {
     }
 }
 
-fn path_to_slash(path: &Path) -> String {
-    path.components()
-        .map(|c| c.as_os_str().to_string_lossy())
-        .collect::<Vec<_>>()
-        .join("/")
-}
-
-fn compute_module_paths(tree: &Tree) -> HashSet<String> {
-    let mut paths = HashSet::new();
-    paths.insert(String::new());
-    for node in &tree.nodes {
-        if node.module.is_none() {
-            continue;
-        }
-        let mut current = node.rel_path.clone();
-        loop {
-            paths.insert(path_to_slash(&current));
-            if let Some(parent) = current.parent() {
-                current = parent.to_path_buf();
-            } else {
-                break;
-            }
-        }
-    }
-    paths
-}
-
 struct RenderContext<'a> {
     tree: &'a Tree,
     chars: TreeChars,
-    module_paths: HashSet<String>,
-    key_dirs: HashSet<String>,
+    selector: Selector<'a>,
+    style: Style,
+    link_base: Option<PathBuf>,
     config: &'a Config,
 }
 
 pub fn render_text(tree: &Tree, workspace: Option<&WorkspaceResolved>, config: &Config) -> String {
-    let module_paths = if config.lens == Lens::Module {
-        compute_module_paths(tree)
-    } else {
-        HashSet::new()
-    };
-    let key_dirs: HashSet<String> = config.key_dirs.iter().cloned().collect();
+    render_text_styled(tree, workspace, config, Style::plain())
+}
+
+pub fn render_text_styled(
+    tree: &Tree,
+    workspace: Option<&WorkspaceResolved>,
+    config: &Config,
+    style: Style,
+) -> String {
     let ctx = RenderContext {
         tree,
         chars: chars(config.unicode),
-        module_paths,
-        key_dirs,
+        selector: Selector::new(tree, config),
+        style,
+        link_base: style.hyperlinks().then(|| absolute_root(&tree.root_path)),
         config,
     };
 
     let mut lines = Vec::new();
     let root = &tree.nodes[tree.root];
-    lines.push(format_root_label(root, workspace));
+    let root_link = ctx.link_target(root);
+    let mut root_label = format_root_label(root, workspace, &style, root_link.as_deref());
+    ctx.push_annotation(&mut root_label, root, false);
+    lines.push(root_label);
+    ctx.push_details(tree.root, "", &mut lines);
     ctx.render_children(tree.root, "", &mut lines);
 
     if tree.truncated {
-        lines.push(format!(
+        lines.push(style.notice(&format!(
             "... (truncated after reaching max-items={})",
             config.max_items
-        ));
+        )));
     }
 
     lines.join("\n")
 }
 
 impl<'a> RenderContext<'a> {
+    /// The `file://` URL a node's label links to, when hyperlinks are on.
+    /// Error nodes are not linked since their path may not be readable.
+    fn link_target(&self, node: &Node) -> Option<String> {
+        let base = self.link_base.as_ref()?;
+        if node.kind == NodeKind::Error {
+            return None;
+        }
+        let mut path = base.join(&node.rel_path);
+        if self.config.link_manifest {
+            if let Some(marker) = node.module.as_ref().and_then(|m| m.markers.first()) {
+                path = path.join(marker);
+            }
+        }
+        Some(file_url(&path))
+    }
+
+    /// Lens-specific lines printed under a node's label, indented so the
+    /// tree lines of its children continue past them.
+    fn detail_lines(&self, node: &Node) -> Vec<String> {
+        if self.config.lens == Lens::Infra {
+            return infra_lines(node);
+        }
+        let Some(module) = &node.module else {
+            return Vec::new();
+        };
+        match self.config.lens {
+            Lens::Deps => vec![
+                format!("depends on: {}", module_list(&module.dependencies)),
+                format!("used by: {}", module_list(&module.dependents)),
+            ],
+            Lens::Entrypoints => entrypoint_lines(&module.entrypoints),
+            Lens::Tests => match &module.tests {
+                Some(tests) if tests.files > 0 => vec![test_line(tests)],
+                _ => Vec::new(),
+            },
+            Lens::Docs => module.docs.as_ref().map(docs_lines).unwrap_or_default(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Lens-specific text appended to a node's label.
+    fn push_annotation(&self, label: &mut String, node: &Node, is_key_dir: bool) {
+        if let Some(affected) = node.module.as_ref().and_then(|m| m.affected) {
+            let tag = format!("[{}]", affected.label());
+            label.push_str(&format!("  {}", self.style.notice(&tag)));
+        }
+        match self.config.lens {
+            Lens::Owners if !node.owners.is_empty() && (node.module.is_some() || is_key_dir) => {
+                let owners = format!("owners: {}", node.owners.join(" "));
+                label.push_str(&format!("  {}", self.style.summary(&owners)));
+            }
+            Lens::Owners if node.module.is_some() => {
+                label.push_str(&format!("  {}", self.style.error("[no owner]")));
+            }
+            Lens::Churn => {
+                if let Some(churn) = node.churn.filter(|c| c.commits > 0) {
+                    let unit = if churn.commits == 1 {
+                        "commit"
+                    } else {
+                        "commits"
+                    };
+                    let text = format!(
+                        "({} {unit}, +{} -{})",
+                        churn.commits, churn.added, churn.deleted
+                    );
+                    label.push_str(&format!("  {}", self.style.summary(&text)));
+                    if churn.hot {
+                        label.push_str(&format!("  {}", self.style.notice("[hot]")));
+                    }
+                }
+            }
+            Lens::Languages if node.is_dir() && !node.languages.is_empty() => {
+                let mut breakdown = language_breakdown(&node.languages);
+                if node.languages_partial {
+                    breakdown.push_str(" (partial)");
+                }
+                label.push_str(&format!("  {}", self.style.summary(&breakdown)));
+            }
+            Lens::Tests
+                if node.module.as_ref().is_some_and(|m| {
+                    m.tests
+                        .as_ref()
+                        .is_some_and(|tests| tests.files == 0 && !tests.partial)
+                }) =>
+            {
+                label.push_str(&format!("  {}", self.style.error("[no tests]")));
+            }
+            Lens::Docs
+                if node
+                    .module
+                    .as_ref()
+                    .is_some_and(|m| m.docs.as_ref().is_some_and(|docs| docs.readme.is_none())) =>
+            {
+                label.push_str(&format!("  {}", self.style.error("[no readme]")));
+            }
+            Lens::Size => {
+                if let Some(size) = node.size {
+                    let text = if node.is_dir() {
+                        let unit = if size.files == 1 { "file" } else { "files" };
+                        let more = if size.partial { "+" } else { "" };
+                        format!(
+                            "({}{more}, {}{more} {unit})",
+                            ByteSize(size.bytes),
+                            size.files
+                        )
+                    } else {
+                        format!("({})", ByteSize(size.bytes))
+                    };
+                    label.push_str(&format!("  {}", self.style.summary(&text)));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn push_details(&self, node_id: usize, prefix: &str, lines: &mut Vec<String>) {
+        let details = self.detail_lines(&self.tree.nodes[node_id]);
+        if details.is_empty() {
+            return;
+        }
+        let has_children =
+            self.tree.nodes[node_id].is_dir() && !self.selector.children(node_id).is_empty();
+        let indent = if has_children {
+            self.chars.vert
+        } else {
+            self.chars.space
+        };
+        for detail in details {
+            lines.push(format!("{prefix}{indent}{}", self.style.summary(&detail)));
+        }
+    }
+
     fn render_children(&self, node_id: usize, prefix: &str, lines: &mut Vec<String>) {
-        let children = self.select_children(node_id);
+        let children = self.selector.children(node_id);
         if children.is_empty() {
             return;
         }
+        let parent_is_module = self.tree.nodes[node_id].module.is_some();
 
         let omitted = if children.len() > self.config.max_children {
             children.len() - self.config.max_children
@@ -121,13 +241,19 @@ impl<'a> RenderContext<'a> {
             } else {
                 self.chars.mid
             };
-            let label = format_node_label(child);
+            let is_key_dir = child.kind == NodeKind::Dir
+                && parent_is_module
+                && self.config.key_dirs.contains(&child.name);
+            let link = self.link_target(child);
+            let mut label = format_node_label(child, &self.style, is_key_dir, link.as_deref());
+            self.push_annotation(&mut label, child, is_key_dir);
             lines.push(format!("{prefix}{connector}{label}"));
             let new_prefix = if is_last {
                 format!("{prefix}{}", self.chars.space)
             } else {
                 format!("{prefix}{}", self.chars.vert)
             };
+            self.push_details(*child_id, &new_prefix, lines);
             if child.kind == NodeKind::Dir {
                 self.render_children(*child_id, &new_prefix, lines);
             }
@@ -135,106 +261,243 @@ impl<'a> RenderContext<'a> {
 
         if omitted > 0 {
             let connector = self.chars.end;
-            let more_label = format!("... ({omitted} more)");
+            let more_label = self.style.notice(&format!("... ({omitted} more)"));
             lines.push(format!("{prefix}{connector}{more_label}"));
         }
     }
+}
 
-    fn select_children(&self, node_id: usize) -> Vec<usize> {
-        let parent = &self.tree.nodes[node_id];
-        let parent_is_module = parent.module.is_some();
-        let marker_files: HashSet<String> = parent
-            .module
-            .as_ref()
-            .map(|m| m.markers.clone())
-            .unwrap_or_default()
-            .into_iter()
-            .collect();
+/// Shares of the top languages by lines (by files when nothing has lines),
+/// e.g. `82% Rust, 12% TOML`. Shares under 1% are left out.
+fn language_breakdown(languages: &[LanguageCount]) -> String {
+    const SHOWN: usize = 3;
+    let total_lines: u64 = languages.iter().map(|l| l.lines).sum();
+    let by_lines = total_lines > 0;
+    let total = if by_lines {
+        total_lines
+    } else {
+        languages.iter().map(|l| l.files).sum()
+    };
+    let parts: Vec<String> = languages
+        .iter()
+        .map(|l| {
+            let amount = if by_lines { l.lines } else { l.files };
+            (l.language, amount * 100 / total.max(1))
+        })
+        .filter(|(_, percent)| *percent >= 1)
+        .take(SHOWN)
+        .map(|(language, percent)| format!("{percent}% {language}"))
+        .collect();
+    if parts.is_empty() {
+        languages[0].language.to_string()
+    } else {
+        parts.join(", ")
+    }
+}
 
-        let mut children: Vec<usize> = parent.children.clone();
-        children.retain(|child_id| {
-            if self.config.lens == Lens::Files {
-                return true;
-            }
-            let child = &self.tree.nodes[*child_id];
-            let child_path = path_to_slash(&child.rel_path);
-            match child.kind {
-                NodeKind::Dir => {
-                    let is_key_dir = parent_is_module && self.key_dirs.contains(&child.name);
-                    let in_module_path = self.module_paths.contains(&child_path);
-                    is_key_dir || in_module_path
-                }
-                NodeKind::File | NodeKind::Error => {
-                    if parent_is_module {
-                        marker_files.contains(&child.name)
-                    } else {
-                        false
-                    }
-                }
+/// Test file count and locations, e.g. `tests: 4 files in tests/, src`.
+fn test_line(tests: &TestInfo) -> String {
+    const SHOWN: usize = 4;
+    let unit = if tests.files == 1 { "file" } else { "files" };
+    let mut locations = tests
+        .locations
+        .iter()
+        .take(SHOWN)
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(", ");
+    if tests.locations.len() > SHOWN {
+        locations.push_str(&format!(" (+{} more)", tests.locations.len() - SHOWN));
+    }
+    let more = if tests.partial { "+" } else { "" };
+    format!("tests: {}{more} {unit} in {locations}", tests.files)
+}
+
+/// The documentation a module has, then what it lacks, e.g.
+/// `docs: README.md (Payments API), LICENSE` and `missing: changelog, docs/`.
+fn docs_lines(docs: &DocsInfo) -> Vec<String> {
+    let mut present = Vec::new();
+    let mut missing = Vec::new();
+    match (&docs.readme, &docs.title) {
+        (Some(readme), Some(title)) => present.push(format!("{readme} ({title})")),
+        (Some(readme), None) => present.push(readme.clone()),
+        (None, _) => missing.push("readme"),
+    }
+    let others = [
+        (docs.changelog.clone(), "changelog"),
+        (docs.docs_dir.as_ref().map(|dir| format!("{dir}/")), "docs/"),
+        (docs.license.clone(), "license"),
+    ];
+    for (found, name) in others {
+        match found {
+            Some(found) => present.push(found),
+            None => missing.push(name),
+        }
+    }
+    let mut lines = Vec::new();
+    if present.is_empty() {
+        lines.push("docs: (none)".to_string());
+    } else {
+        lines.push(format!("docs: {}", present.join(", ")));
+    }
+    if !missing.is_empty() {
+        lines.push(format!("missing: {}", missing.join(", ")));
+    }
+    lines
+}
+
+/// One line per infra kind, e.g. `docker: Dockerfile (node:20-alpine)`.
+/// Files outside the node are shown relative to it (`../docker-compose.yml`).
+fn infra_lines(node: &Node) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = None;
+    for file in &node.infra {
+        let mut path = relative_path(&node.rel_path, &file.path);
+        if matches!(file.kind, InfraKind::Helm | InfraKind::Terraform) && path != "." {
+            path.push('/');
+        }
+        let item = match &file.detail {
+            Some(detail) => format!("{path} ({detail})"),
+            None => path,
+        };
+        if current == Some(file.kind) {
+            if let Some(line) = lines.last_mut() {
+                line.push_str(&format!(", {item}"));
             }
-        });
+        } else {
+            lines.push(format!("{}: {item}", file.kind.label()));
+            current = Some(file.kind);
+        }
+    }
+    lines
+}
+
+/// `to` as seen from the directory `from`, both relative to the root.
+fn relative_path(from: &Path, to: &Path) -> String {
+    if let Ok(inside) = to.strip_prefix(from) {
+        return match path_to_slash(inside) {
+            slashed if slashed.is_empty() => ".".to_string(),
+            slashed => slashed,
+        };
+    }
+    let common = from
+        .components()
+        .zip(to.components())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let ups = from.components().count() - common;
+    let rest: PathBuf = to.components().skip(common).collect();
+    format!("{}{}", "../".repeat(ups), path_to_slash(&rest))
+}
 
-        children.sort_by(|a, b| {
-            let na = &self.tree.nodes[*a];
-            let nb = &self.tree.nodes[*b];
-            let ka = kind_rank(na.kind);
-            let kb = kind_rank(nb.kind);
-            ka.cmp(&kb)
-                .then_with(|| na.name.to_lowercase().cmp(&nb.name.to_lowercase()))
-                .then_with(|| na.name.cmp(&nb.name))
-        });
+/// One line per entrypoint kind, e.g. `bin: cli (bin/cli.js), admin`.
+fn entrypoint_lines(entrypoints: &[Entrypoint]) -> Vec<String> {
+    if entrypoints.is_empty() {
+        return vec!["entrypoints: (none)".to_string()];
+    }
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = None;
+    for entry in entrypoints {
+        let item = match &entry.target {
+            Some(target) if *target != entry.name => format!("{} ({target})", entry.name),
+            _ => entry.name.clone(),
+        };
+        if current == Some(entry.kind) {
+            if let Some(line) = lines.last_mut() {
+                line.push_str(&format!(", {item}"));
+            }
+        } else {
+            let heading = match entry.kind {
+                EntrypointKind::Bin => "bin",
+                EntrypointKind::Main => "main",
+                EntrypointKind::Export => "exports",
+                EntrypointKind::Script => "scripts",
+            };
+            lines.push(format!("{heading}: {item}"));
+            current = Some(entry.kind);
+        }
+    }
+    lines
+}
 
-        children
+/// Comma-separated module paths, with `.` for the root module.
+fn module_list(paths: &[PathBuf]) -> String {
+    if paths.is_empty() {
+        return "(none)".to_string();
     }
+    paths
+        .iter()
+        .map(|path| match path_to_slash(path) {
+            slashed if slashed.is_empty() => ".".to_string(),
+            slashed => slashed,
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
-fn format_root_label(node: &crate::model::Node, workspace: Option<&WorkspaceResolved>) -> String {
-    let mut label = if node.kind == NodeKind::Dir {
+fn absolute_root(root: &Path) -> PathBuf {
+    fs::canonicalize(root).unwrap_or_else(|_| {
+        std::env::current_dir()
+            .map(|cwd| cwd.join(root))
+            .unwrap_or_else(|_| root.to_path_buf())
+    })
+}
+
+fn format_root_label(
+    node: &Node,
+    workspace: Option<&WorkspaceResolved>,
+    style: &Style,
+    link: Option<&str>,
+) -> String {
+    let name = if node.kind == NodeKind::Dir {
         format!("{}/", node.name)
     } else {
         node.name.clone()
     };
+    let mut label = match link {
+        Some(url) => style.link(url, &name),
+        None => name,
+    };
 
     if let Some(workspace) = workspace {
-        label.push_str(&format!("  [workspace: {}]", workspace.kind.label()));
-    }
-
-    if let Some(module) = &node.module {
-        label.push_str(&format!("  {}", module.kind.tag()));
-        if let Some(summary) = &module.summary {
-            label.push_str(&format!("  {}", summary));
-        }
+        let tag = format!("[workspace: {}]", workspace.kind.label());
+        label.push_str(&format!("  {}", style.highlight(&tag)));
     }
 
+    push_module(&mut label, node, style);
     label
 }
 
-fn format_node_label(node: &crate::model::Node) -> String {
+fn format_node_label(node: &Node, style: &Style, is_key_dir: bool, link: Option<&str>) -> String {
     let mut base = if node.kind == NodeKind::Dir {
-        format!("{}/", node.name)
+        let name = format!("{}/", node.name);
+        if is_key_dir {
+            style.highlight(&name)
+        } else {
+            name
+        }
     } else if node.kind == NodeKind::Error {
         if let Some(err) = &node.error {
-            format!("{} ({})", node.name, err)
+            style.error(&format!("{} ({})", node.name, err))
         } else {
-            node.name.clone()
+            style.error(&node.name)
         }
     } else {
         node.name.clone()
     };
-
-    if let Some(module) = &node.module {
-        base.push_str(&format!("  {}", module.kind.tag()));
-        if let Some(summary) = &module.summary {
-            base.push_str(&format!("  {}", summary));
-        }
+    if let Some(url) = link {
+        base = style.link(url, &base);
     }
 
+    push_module(&mut base, node, style);
     base
 }
 
-fn kind_rank(kind: NodeKind) -> u8 {
-    match kind {
-        NodeKind::Dir => 0,
-        NodeKind::File | NodeKind::Error => 1,
+fn push_module(label: &mut String, node: &Node, style: &Style) {
+    if let Some(module) = &node.module {
+        label.push_str(&format!("  {}", style.module_tag(module.kind)));
+        if let Some(summary) = &module.summary {
+            label.push_str(&format!("  {}", style.summary(summary)));
+        }
     }
 }
@@ -0,0 +1,7 @@
+module example.com/go-svc
+
+go 1.22
+
+require example.com/golib v0.0.0
+
+replace example.com/golib => ../golib
@@ -0,0 +1 @@
+from py_tool.cli import main
@@ -0,0 +1,5 @@
+plugins {
+    id("com.android.application")
+}
+
+description = "Android note-taking app"
@@ -0,0 +1,2 @@
+plugins { `kotlin-dsl` }
+description = "Convention plugins"
@@ -0,0 +1,3 @@
+plugins { kotlin("jvm") }
+group = "com.example.notes.core"
+description = "Storage and sync"
@@ -0,0 +1,17 @@
+pluginManagement {
+    repositories {
+        maven { url = uri("https://plugins.example.com/m2") } // mirror
+    }
+}
+
+rootProject.name = "notes"
+
+include(
+    ":app",
+    ":lib:core",
+    ":lib:ui",
+)
+include(":legacy")
+project(":legacy").projectDir = file("old/legacy-module")
+
+includeBuild("build-logic")
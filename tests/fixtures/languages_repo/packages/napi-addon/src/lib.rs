@@ -0,0 +1,82 @@
+use std::os::raw::c_int;
+
+pub fn add_0(a: c_int, b: c_int) -> c_int {
+    a + b
+}
+
+pub fn add_1(a: c_int, b: c_int) -> c_int {
+    a + b
+}
+
+pub fn add_2(a: c_int, b: c_int) -> c_int {
+    a + b
+}
+
+pub fn add_3(a: c_int, b: c_int) -> c_int {
+    a + b
+}
+
+pub fn add_4(a: c_int, b: c_int) -> c_int {
+    a + b
+}
+
+pub fn add_5(a: c_int, b: c_int) -> c_int {
+    a + b
+}
+
+pub fn add_6(a: c_int, b: c_int) -> c_int {
+    a + b
+}
+
+pub fn add_7(a: c_int, b: c_int) -> c_int {
+    a + b
+}
+
+pub fn add_8(a: c_int, b: c_int) -> c_int {
+    a + b
+}
+
+pub fn add_9(a: c_int, b: c_int) -> c_int {
+    a + b
+}
+
+pub fn add_10(a: c_int, b: c_int) -> c_int {
+    a + b
+}
+
+pub fn add_11(a: c_int, b: c_int) -> c_int {
+    a + b
+}
+
+pub fn add_12(a: c_int, b: c_int) -> c_int {
+    a + b
+}
+
+pub fn add_13(a: c_int, b: c_int) -> c_int {
+    a + b
+}
+
+pub fn add_14(a: c_int, b: c_int) -> c_int {
+    a + b
+}
+
+pub fn add_15(a: c_int, b: c_int) -> c_int {
+    a + b
+}
+
+pub fn add_16(a: c_int, b: c_int) -> c_int {
+    a + b
+}
+
+pub fn add_17(a: c_int, b: c_int) -> c_int {
+    a + b
+}
+
+pub fn add_18(a: c_int, b: c_int) -> c_int {
+    a + b
+}
+
+pub fn add_19(a: c_int, b: c_int) -> c_int {
+    a + b
+}
+
@@ -0,0 +1 @@
+class ApplicationRecord < ActiveRecord::Base; end
@@ -0,0 +1,4 @@
+module Dummy
+  class Application < Rails::Application
+  end
+end
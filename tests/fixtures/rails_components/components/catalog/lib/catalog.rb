@@ -0,0 +1 @@
+module Catalog; end
@@ -0,0 +1 @@
+module Admin; end
@@ -0,0 +1 @@
+export const handler = () => "ok";
@@ -0,0 +1,3 @@
+module example.com/go-svc
+
+go 1.22
@@ -0,0 +1,5 @@
+package svc
+
+import "testing"
+
+func TestHandle(t *testing.T) { Handle() }
@@ -0,0 +1 @@
+test("e2e", () => {});
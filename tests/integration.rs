@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
@@ -40,7 +41,15 @@ fn node_simple_module_md() {
     run_and_assert(
         "node_simple",
         "expected_module_md.md",
-        &["--lens", "module", "--format", "md", "--ascii"],
+        &[
+            "--lens",
+            "module",
+            "--format",
+            "md",
+            "--md-style",
+            "fence",
+            "--ascii",
+        ],
     );
 }
 
@@ -53,6 +62,197 @@ fn pnpm_monorepo_module_text() {
     );
 }
 
+#[test]
+fn pnpm_monorepo_module_md_list() {
+    run_and_assert(
+        "pnpm_monorepo",
+        "expected_module_md.md",
+        &["--lens", "module", "--format", "md", "--md-style", "list"],
+    );
+}
+
+#[test]
+fn pnpm_monorepo_mermaid() {
+    run_and_assert(
+        "pnpm_monorepo",
+        "expected_mermaid.mmd",
+        &["--format", "mermaid"],
+    );
+}
+
+#[test]
+fn pnpm_monorepo_dot() {
+    run_and_assert("pnpm_monorepo", "expected_module.dot", &["--format", "dot"]);
+}
+
+#[test]
+fn pnpm_monorepo_html_embeds_tree() {
+    let mut cmd = cargo_bin_cmd!("smarttree");
+    cmd.arg(fixture_path("pnpm_monorepo"));
+    cmd.args(["--format", "html", "--max-children", "1"]);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let html = String::from_utf8(output).expect("utf8");
+
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<title>pnpm_monorepo - smarttree</title>"));
+    assert!(html.contains("var maxChildren = 1;"));
+    let start = html.find(r#"type="application/json">"#).expect("data") + 24;
+    let end = start + html[start..].find("</script>").expect("end");
+    let doc: serde_json::Value = serde_json::from_str(&html[start..end]).expect("json");
+    assert_eq!(doc["workspace"]["kind"], "pnpm");
+    assert_eq!(
+        doc["root"]["children"].as_array().expect("children").len(),
+        3
+    );
+}
+
+#[test]
+fn pnpm_monorepo_ndjson_stream() {
+    let mut cmd = cargo_bin_cmd!("smarttree");
+    cmd.arg(fixture_path("pnpm_monorepo"));
+    cmd.args(["--format", "ndjson"]);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let records: Vec<serde_json::Value> = String::from_utf8(output)
+        .expect("utf8")
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("json line"))
+        .collect();
+
+    let end = records.last().expect("end record");
+    assert_eq!(end["type"], "end");
+    assert_eq!(end["workspace"], "pnpm");
+    assert_eq!(end["truncated"], false);
+    assert_eq!(end["count"].as_u64(), Some(records.len() as u64 - 2));
+
+    let web = records
+        .iter()
+        .find(|r| r["path"] == "apps/web")
+        .expect("apps/web");
+    assert_eq!(web["kind"], "dir");
+    assert_eq!(web["depth"], 2);
+    assert_eq!(web["module"]["summary"], "@acme/web - Web app");
+
+    let root = &records[records.len() - 2];
+    assert_eq!(root["path"], "");
+    assert_eq!(root["module"]["kind"], "node");
+    let src = records
+        .iter()
+        .find(|r| r["path"] == "apps/web/src")
+        .expect("src");
+    assert!(src["module"].is_null());
+}
+
+#[test]
+fn bazel_repo_ndjson_modules_match_json() {
+    let modules = ndjson_modules("bazel_repo");
+    assert_eq!(modules, json_modules("bazel_repo"));
+    assert_eq!(modules.get("web").map(String::as_str), Some("bazel"));
+    // A package.json outside any BUILD package is not a module.
+    assert!(!modules.contains_key("tools/gen"));
+}
+
+#[test]
+fn turbo_repo_ndjson_modules_match_json() {
+    let modules = ndjson_modules("turbo_repo");
+    assert_eq!(modules, json_modules("turbo_repo"));
+    assert_eq!(modules.get("packages/ui").map(String::as_str), Some("node"));
+    // Without workspace patterns, only group dirs such as packages/ hold
+    // packages.
+    assert!(!modules.contains_key("tools/codegen"));
+}
+
+#[test]
+fn stale_workspace_patterns_fall_back_to_group_dirs() {
+    run_and_assert(
+        "stale_workspace",
+        "expected_module_text.txt",
+        &["--lens", "module", "--format", "text", "--ascii"],
+    );
+    let modules = ndjson_modules("stale_workspace");
+    assert_eq!(modules, json_modules("stale_workspace"));
+    assert!(modules.contains_key("packages/api"));
+    assert!(!modules.contains_key("tools/release"));
+}
+
+#[test]
+fn color_always_styles_text_output() {
+    let mut cmd = cargo_bin_cmd!("smarttree");
+    cmd.arg(fixture_path("node_simple"));
+    cmd.args(["--color", "always"]);
+    cmd.env("NO_COLOR", "1");
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let text = String::from_utf8(output).expect("utf8");
+    assert!(text.contains("\u{1b}[1;32m[node]\u{1b}[0m"));
+    assert!(text.contains("\u{1b}[2mnode-simple - A simple node app\u{1b}[0m"));
+    assert!(text.contains("\u{1b}[1msrc/\u{1b}[0m"));
+}
+
+#[test]
+fn color_auto_is_plain_when_piped() {
+    run_and_assert(
+        "node_simple",
+        "expected_module_text.txt",
+        &["--color", "auto", "--ascii"],
+    );
+}
+
+#[test]
+fn hyperlinks_always_links_nodes() {
+    let mut cmd = cargo_bin_cmd!("smarttree");
+    cmd.arg(fixture_path("node_simple"));
+    cmd.args([
+        "--hyperlinks",
+        "always",
+        "--link-manifest",
+        "--color",
+        "never",
+    ]);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let text = String::from_utf8(output).expect("utf8");
+    let first = text.lines().next().expect("root line");
+    assert!(first.starts_with("\u{1b}]8;;file://"));
+    assert!(first.contains("/node_simple/package.json\u{1b}\\node_simple/\u{1b}]8;;\u{1b}\\"));
+    assert!(text.contains("/node_simple/src\u{1b}\\src/\u{1b}]8;;\u{1b}\\"));
+}
+
+#[test]
+fn context_pack_respects_token_budget() {
+    let mut cmd = cargo_bin_cmd!("smarttree");
+    cmd.arg(fixture_path("pnpm_monorepo"));
+    cmd.args(["--format", "context", "--token-budget", "220"]);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let text = String::from_utf8(output).expect("utf8");
+
+    assert!(text.starts_with("# Project context: pnpm_monorepo\n"));
+    assert!(text.contains("|-- packages/\n"));
+    let root = text
+        .find("===== file: package.json =====")
+        .expect("root manifest");
+    let api = text
+        .find("===== file: apps/api/package.json =====")
+        .expect("api manifest");
+    assert!(root < api);
+    assert!(text.contains("(omitted to fit token budget of 220: apps/web/package.json"));
+}
+
+#[test]
+fn pnpm_monorepo_mdtable() {
+    run_and_assert(
+        "pnpm_monorepo",
+        "expected_modules_table.md",
+        &["--format", "mdtable"],
+    );
+}
+
+#[test]
+fn rust_workspace_csv_columns() {
+    run_and_assert(
+        "rust_workspace",
+        "expected_modules.csv",
+        &["--format", "csv", "--columns", "path,kind,summary"],
+    );
+}
+
 #[test]
 fn rust_workspace_module_text() {
     run_and_assert(
@@ -62,6 +262,265 @@ fn rust_workspace_module_text() {
     );
 }
 
+#[test]
+fn deps_workspace_deps_text() {
+    run_and_assert(
+        "deps_workspace",
+        "expected_deps_text.txt",
+        &["--lens", "deps", "--format", "text", "--ascii"],
+    );
+}
+
+#[test]
+fn owners_repo_owners_text() {
+    run_and_assert(
+        "owners_repo",
+        "expected_owners_text.txt",
+        &["--lens", "owners", "--format", "text", "--ascii"],
+    );
+}
+
+#[test]
+fn size_repo_sorted_with_threshold() {
+    run_and_assert(
+        "size_repo",
+        "expected_size_text.txt",
+        &[
+            "--lens",
+            "size",
+            "--sort",
+            "size",
+            "--min-size",
+            "64",
+            "--ignore",
+            "expected_*",
+            "--ascii",
+        ],
+    );
+}
+
+#[test]
+fn size_below_depth_limit_is_bounded_by_max_items() {
+    let mut cmd = cargo_bin_cmd!("smarttree");
+    cmd.arg(fixture_path("size_repo")).args([
+        "--lens",
+        "size",
+        "--depth",
+        "1",
+        "--max-items",
+        "4",
+        "--ignore",
+        "expected_*",
+        "--ascii",
+    ]);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let text = String::from_utf8(output).expect("utf8");
+    assert!(text.contains("packages/  (52 B+, 3+ files)"), "{text}");
+}
+
+#[test]
+fn sort_size_orders_entries_under_other_lenses() {
+    let mut cmd = cargo_bin_cmd!("smarttree");
+    cmd.arg(fixture_path("size_repo"))
+        .args(["--lens", "files", "--sort", "size", "--ascii"]);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let text = String::from_utf8(output).expect("utf8");
+    let sprites = text.find("sprites.txt").expect("sprites.txt");
+    let icons = text.find("icons.txt").expect("icons.txt");
+    assert!(sprites < icons, "{text}");
+}
+
+#[test]
+fn entrypoints_repo_entrypoints_text() {
+    run_and_assert(
+        "entrypoints_repo",
+        "expected_entrypoints_text.txt",
+        &["--lens", "entrypoints", "--format", "text", "--ascii"],
+    );
+}
+
+#[test]
+fn languages_repo_languages_text() {
+    run_and_assert(
+        "languages_repo",
+        "expected_languages_text.txt",
+        &["--lens", "languages", "--ignore", "expected_*", "--ascii"],
+    );
+}
+
+#[test]
+fn languages_below_depth_limit_are_counted() {
+    let dir = tempdir().expect("tempdir");
+    let root = dir.path();
+    write_file(root, "package.json", r#"{ "name": "app" }"#);
+    write_file(root, "src/main/java/com/acme/App.java", "class App {\n}\n");
+    write_file(
+        root,
+        "src/main/java/com/acme/Util.java",
+        "class Util {\n}\n",
+    );
+
+    let mut cmd = cargo_bin_cmd!("smarttree");
+    cmd.arg(root)
+        .args(["--lens", "languages", "--depth", "2", "--ascii"]);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let text = String::from_utf8(output).expect("utf8");
+    assert!(text.contains("src/  100% Java\n"), "{text}");
+
+    for i in 0..10 {
+        write_file(root, &format!("src/main/java/T{i}.java"), "class T {}\n");
+    }
+    let mut cmd = cargo_bin_cmd!("smarttree");
+    cmd.arg(root).args([
+        "--lens",
+        "languages",
+        "--depth",
+        "2",
+        "--max-items",
+        "6",
+        "--ascii",
+    ]);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let text = String::from_utf8(output).expect("utf8");
+    assert!(text.contains("src/  100% Java (partial)\n"), "{text}");
+}
+
+#[test]
+fn tests_repo_tests_text() {
+    run_and_assert(
+        "tests_repo",
+        "expected_tests_text.txt",
+        &["--lens", "tests", "--format", "text", "--ascii"],
+    );
+}
+
+#[test]
+fn tests_below_depth_limit_are_found() {
+    let dir = tempdir().expect("tempdir");
+    let root = dir.path();
+    write_file(root, "package.json", r#"{ "workspaces": ["packages/*"] }"#);
+    write_file(root, "packages/svc/package.json", r#"{ "name": "svc" }"#);
+    write_file(
+        root,
+        "packages/svc/src/test/java/com/acme/AppTest.java",
+        "class AppTest {}\n",
+    );
+    write_file(root, "packages/web/package.json", r#"{ "name": "web" }"#);
+    write_file(
+        root,
+        "packages/web/src/components/__tests__/button.js",
+        "test();\n",
+    );
+    write_file(root, "packages/ui/package.json", r#"{ "name": "ui" }"#);
+    write_file(root, "packages/ui/src/components/deep/button.js", "\n");
+
+    let mut cmd = cargo_bin_cmd!("smarttree");
+    cmd.arg(root).args(["--lens", "tests", "--ascii"]);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let text = String::from_utf8(output).expect("utf8");
+    assert!(text.contains("tests: 1 file in src/test/"), "{text}");
+    assert!(
+        text.contains("tests: 1 file in src/components/__tests__/"),
+        "{text}"
+    );
+    assert!(text.contains("ui/  [node]  ui  [no tests]"), "{text}");
+
+    for i in 0..20 {
+        write_file(
+            root,
+            &format!("packages/svc/src/test/java/T{i}Test.java"),
+            "\n",
+        );
+    }
+    let mut cmd = cargo_bin_cmd!("smarttree");
+    cmd.arg(root)
+        .args(["--lens", "tests", "--ascii", "--max-items", "18"]);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let text = String::from_utf8(output).expect("utf8");
+    assert!(text.contains("+ files in src/test/"), "{text}");
+}
+
+#[test]
+fn docs_repo_docs_text() {
+    run_and_assert(
+        "docs_repo",
+        "expected_docs_text.txt",
+        &["--lens", "docs", "--format", "text", "--ascii"],
+    );
+}
+
+#[test]
+fn infra_repo_infra_text() {
+    run_and_assert(
+        "infra_repo",
+        "expected_infra_text.txt",
+        &["--lens", "infra", "--format", "text", "--ascii"],
+    );
+}
+
+#[test]
+fn maven_multimodule_module_text() {
+    run_and_assert(
+        "maven_multimodule",
+        "expected_module_text.txt",
+        &["--lens", "module", "--format", "text", "--ascii"],
+    );
+}
+
+#[test]
+fn gradle_multiproject_module_text() {
+    run_and_assert(
+        "gradle_multiproject",
+        "expected_module_text.txt",
+        &["--lens", "module", "--format", "text", "--ascii"],
+    );
+}
+
+#[test]
+fn dotnet_solution_module_text() {
+    run_and_assert(
+        "dotnet_solution",
+        "expected_module_text.txt",
+        &["--lens", "module", "--format", "text", "--ascii"],
+    );
+}
+
+#[test]
+fn bazel_repo_module_text() {
+    run_and_assert(
+        "bazel_repo",
+        "expected_module_text.txt",
+        &["--lens", "module", "--format", "text", "--ascii"],
+    );
+}
+
+#[test]
+fn rails_components_module_text() {
+    run_and_assert(
+        "rails_components",
+        "expected_module_text.txt",
+        &["--lens", "module", "--format", "text", "--ascii"],
+    );
+}
+
+#[test]
+fn react_native_app_stays_node_module() {
+    run_and_assert(
+        "react_native_app",
+        "expected_module_text.txt",
+        &["--lens", "module", "--format", "text", "--ascii"],
+    );
+}
+
+#[test]
+fn nuxt_app_with_fastlane_gemfile_is_not_rails() {
+    run_and_assert(
+        "nuxt_app",
+        "expected_module_text.txt",
+        &["--lens", "module", "--format", "text", "--ascii"],
+    );
+}
+
 #[test]
 fn python_project_module_text() {
     run_and_assert(
@@ -100,3 +559,271 @@ fn init_respects_custom_config_path() {
     let content = fs::read_to_string(config_path).expect("config file");
     assert!(content.contains("max_children: 200"));
 }
+
+fn run_json(fixture: &str, args: &[&str]) -> serde_json::Value {
+    let mut cmd = cargo_bin_cmd!("smarttree");
+    cmd.arg(fixture_path(fixture));
+    cmd.args(["--format", "json"]);
+    cmd.args(args);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    serde_json::from_slice(&output).expect("valid json")
+}
+
+fn child_names(node: &serde_json::Value) -> Vec<&str> {
+    node["children"]
+        .as_array()
+        .expect("children")
+        .iter()
+        .map(|c| c["name"].as_str().expect("name"))
+        .collect()
+}
+
+/// `path -> module kind` for every module in the `--format ndjson` stream.
+fn ndjson_modules(fixture: &str) -> BTreeMap<String, String> {
+    let mut cmd = cargo_bin_cmd!("smarttree");
+    cmd.arg(fixture_path(fixture));
+    cmd.args(["--format", "ndjson"]);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    String::from_utf8(output)
+        .expect("utf8")
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).expect("json line"))
+        .filter(|record| record["type"] == "node" && !record["module"].is_null())
+        .map(|record| {
+            let path = record["path"].as_str().expect("path").to_string();
+            let kind = record["module"]["kind"].as_str().expect("kind").to_string();
+            (path, kind)
+        })
+        .collect()
+}
+
+/// `path -> module kind` for every module in the `--format json --unfiltered`
+/// tree.
+fn json_modules(fixture: &str) -> BTreeMap<String, String> {
+    fn collect(node: &serde_json::Value, modules: &mut BTreeMap<String, String>) {
+        if let Some(kind) = node["module"]["kind"].as_str() {
+            let path = node["path"].as_str().expect("path").to_string();
+            modules.insert(path, kind.to_string());
+        }
+        for child in node["children"].as_array().into_iter().flatten() {
+            collect(child, modules);
+        }
+    }
+
+    let doc = run_json(fixture, &["--unfiltered"]);
+    let mut modules = BTreeMap::new();
+    collect(&doc["root"], &mut modules);
+    modules
+}
+
+#[test]
+fn pnpm_monorepo_module_json() {
+    let doc = run_json("pnpm_monorepo", &["--lens", "module"]);
+    assert_eq!(doc["schema_version"], 1);
+    assert_eq!(doc["lens"], "module");
+    assert_eq!(doc["filtered"], true);
+    assert_eq!(doc["truncated"], false);
+    assert_eq!(doc["workspace"]["kind"], "pnpm");
+    let roots = doc["workspace"]["package_roots"].as_array().expect("roots");
+    for expected in ["apps/api", "apps/web", "packages/ui", "packages/utils"] {
+        assert!(roots.iter().any(|r| r == expected), "missing {expected}");
+    }
+
+    let root = &doc["root"];
+    assert_eq!(root["path"], "");
+    assert_eq!(child_names(root), vec!["apps", "packages", "package.json"]);
+
+    let ui = &root["children"][1]["children"][0];
+    assert_eq!(ui["path"], "packages/ui");
+    assert_eq!(ui["kind"], "dir");
+    assert_eq!(ui["module"]["kind"], "node");
+    assert_eq!(ui["module"]["tag"], "[node]");
+    assert_eq!(ui["module"]["summary"], "@acme/ui - UI components");
+    assert_eq!(ui["module"]["markers"], serde_json::json!(["package.json"]));
+}
+
+#[test]
+fn pnpm_monorepo_unfiltered_json() {
+    let doc = run_json("pnpm_monorepo", &["--lens", "module", "--unfiltered"]);
+    assert_eq!(doc["filtered"], false);
+    let names = child_names(&doc["root"]);
+    assert!(names.contains(&"pnpm-workspace.yaml"));
+    assert!(names.contains(&"expected_module_text.txt"));
+}
+
+#[test]
+fn deps_workspace_deps_json() {
+    let doc = run_json("deps_workspace", &["--lens", "deps"]);
+    assert_eq!(doc["lens"], "deps");
+    let packages = &doc["root"]["children"][0];
+    let lib = packages["children"]
+        .as_array()
+        .expect("children")
+        .iter()
+        .find(|c| c["path"] == "packages/lib")
+        .expect("lib");
+    assert_eq!(
+        lib["module"]["dependents"],
+        serde_json::json!(["packages/app"])
+    );
+    assert!(lib["module"].get("dependencies").is_none());
+}
+
+fn git(dir: &std::path::Path, args: &[&str]) {
+    let status = std::process::Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args([
+            "-c",
+            "user.name=smarttree",
+            "-c",
+            "user.email=smarttree@example.com",
+            "-c",
+            "commit.gpgsign=false",
+        ])
+        .args(args)
+        .status()
+        .expect("run git");
+    assert!(status.success(), "git {args:?} failed");
+}
+
+fn write_file(dir: &std::path::Path, rel: &str, content: &str) {
+    let path = dir.join(rel);
+    fs::create_dir_all(path.parent().expect("parent")).expect("mkdir");
+    fs::write(path, content).expect("write");
+}
+
+#[test]
+fn churn_lens_counts_commits_per_module() {
+    let dir = tempdir().expect("tempdir");
+    let root = dir.path();
+    git(root, &["init", "-q"]);
+    write_file(root, "package.json", r#"{ "workspaces": ["packages/*"] }"#);
+    write_file(root, "packages/busy/package.json", r#"{ "name": "busy" }"#);
+    write_file(
+        root,
+        "packages/quiet/package.json",
+        r#"{ "name": "quiet" }"#,
+    );
+    git(root, &["add", "-A"]);
+    git(root, &["commit", "-q", "-m", "initial"]);
+    for round in 0..3 {
+        write_file(root, "packages/busy/index.js", &format!("// {round}\n"));
+        git(root, &["add", "-A"]);
+        git(root, &["commit", "-q", "-m", "busy"]);
+    }
+
+    let mut cmd = cargo_bin_cmd!("smarttree");
+    cmd.arg(root).args(["--lens", "churn", "--ascii"]);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let text = String::from_utf8(output).expect("utf8");
+    assert!(
+        text.contains("busy/  [node]  busy  (4 commits, +4 -2)  [hot]"),
+        "{text}"
+    );
+    assert!(
+        text.contains("quiet/  [node]  quiet  (1 commit, +1 -0)\n"),
+        "{text}"
+    );
+}
+
+#[test]
+fn changed_since_shows_affected_modules() {
+    let dir = tempdir().expect("tempdir");
+    let root = dir.path();
+    git(root, &["init", "-q"]);
+    write_file(root, "package.json", r#"{ "workspaces": ["packages/*"] }"#);
+    write_file(root, "packages/core/package.json", r#"{ "name": "core" }"#);
+    write_file(
+        root,
+        "packages/app/package.json",
+        r#"{ "name": "app", "dependencies": { "core": "workspace:*" } }"#,
+    );
+    write_file(
+        root,
+        "packages/other/package.json",
+        r#"{ "name": "other" }"#,
+    );
+    write_file(root, "packages/docs/package.json", r#"{ "name": "docs" }"#);
+    git(root, &["add", "-A"]);
+    git(root, &["commit", "-q", "-m", "initial"]);
+    git(root, &["tag", "base"]);
+
+    write_file(root, "packages/core/src/deep/nested/index.js", "// new\n");
+    git(root, &["add", "-A"]);
+    git(root, &["commit", "-q", "-m", "core"]);
+    write_file(root, "packages/docs/guide.md", "untracked\n");
+
+    let mut cmd = cargo_bin_cmd!("smarttree");
+    cmd.arg(root).args([
+        "--changed-since",
+        "base",
+        "--format",
+        "csv",
+        "--columns",
+        "path",
+    ]);
+    cmd.assert()
+        .success()
+        .stdout("path\npackages/core\npackages/docs\n");
+
+    let mut cmd = cargo_bin_cmd!("smarttree");
+    cmd.arg(root)
+        .args(["--changed-since", "base", "--include-dependents", "--ascii"]);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let text = String::from_utf8(output).expect("utf8");
+    assert!(text.contains("app/  [node]  app  [dependent]"), "{text}");
+    assert!(text.contains("core/  [node]  core  [changed]"), "{text}");
+    assert!(!text.contains("other/"), "{text}");
+}
+
+#[test]
+fn changed_since_handles_paths_git_would_quote() {
+    let dir = tempdir().expect("tempdir");
+    let root = dir.path();
+    git(root, &["init", "-q"]);
+    write_file(root, "package.json", r#"{ "workspaces": ["packages/*"] }"#);
+    write_file(root, "packages/café/package.json", r#"{ "name": "cafe" }"#);
+    write_file(root, "packages/my lib/package.json", r#"{ "name": "lib" }"#);
+    write_file(
+        root,
+        "packages/other/package.json",
+        r#"{ "name": "other" }"#,
+    );
+    git(root, &["add", "-A"]);
+    git(root, &["commit", "-q", "-m", "initial"]);
+    git(root, &["tag", "base"]);
+
+    write_file(root, "packages/café/menu.js", "// new\n");
+    git(root, &["add", "-A"]);
+    git(root, &["commit", "-q", "-m", "cafe"]);
+    write_file(root, "packages/my lib/notes draft.md", "untracked\n");
+
+    let mut cmd = cargo_bin_cmd!("smarttree");
+    cmd.arg(root).args([
+        "--changed-since",
+        "base",
+        "--format",
+        "csv",
+        "--columns",
+        "path",
+    ]);
+    cmd.assert()
+        .success()
+        .stdout("path\npackages/café\npackages/my lib\n");
+}
+
+#[test]
+fn changed_since_does_not_pass_ref_as_git_option() {
+    let dir = tempdir().expect("tempdir");
+    let root = dir.path();
+    git(root, &["init", "-q"]);
+    write_file(root, "package.json", r#"{ "name": "app" }"#);
+    git(root, &["add", "-A"]);
+    git(root, &["commit", "-q", "-m", "initial"]);
+
+    let mut cmd = cargo_bin_cmd!("smarttree");
+    cmd.arg(root).arg("--changed-since=--output=diff.txt");
+    cmd.assert().failure();
+    assert!(!root.join("diff.txt").exists());
+}